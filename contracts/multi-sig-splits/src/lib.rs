@@ -263,4 +263,15 @@ impl MultisigSplitsContract {
         let split = storage::get_split(&env, &split_id);
         storage::can_execute(&env, &split)
     }
+
+    /// Get a split's status, or None if it doesn't exist
+    pub fn get_split_status(
+        env: Env,
+        split_id: String,
+    ) -> Option<MultisigStatus> {
+        if !storage::split_exists(&env, &split_id) {
+            return None;
+        }
+        Some(storage::get_split(&env, &split_id).status)
+    }
 }
\ No newline at end of file
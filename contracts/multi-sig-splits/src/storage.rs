@@ -71,7 +71,7 @@ pub fn is_expired(env: &Env, split: &MultisigSplit) -> bool {
 /// Update split status
 pub fn update_split_status(env: &Env, split_id: &String, status: &MultisigStatus) {
     let mut split = get_split(env, split_id);
-    split.status = *status;
+    split.status = status.clone();
     if *status == MultisigStatus::Executed {
         split.executed_at = env.ledger().timestamp();
     }
@@ -294,4 +294,18 @@ fn test_nonexistent_split() {
     // Try to execute nonexistent split
     // let result = client.execute_split(&split_id);
     // assert!(result.is_err());
-}
\ No newline at end of file
+}
+#[test]
+fn test_get_split_status() {
+    let (env, admin, client) = setup_test();
+    let split_id = String::from_str(&env, "split-status");
+
+    client.initialize(&admin);
+    assert_eq!(client.get_split_status(&split_id), None);
+
+    client.create_multisig_split(&split_id, &1, &3600);
+    assert_eq!(client.get_split_status(&split_id), Some(MultisigStatus::Pending));
+
+    client.cancel_split(&split_id, &String::from_str(&env, "No longer needed"));
+    assert_eq!(client.get_split_status(&split_id), Some(MultisigStatus::Cancelled));
+}
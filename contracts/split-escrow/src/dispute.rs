@@ -0,0 +1,62 @@
+//! # Dispute Resolution Interface
+//!
+//! Client for the `dispute-resolution` contract. Escrows open disputes
+//! through this client so only disputes raised by their own creator or
+//! participants are ever consulted.
+
+use soroban_sdk::{
+    contractclient, contracttype, Address, ConversionError, Env, Map, String, Symbol, TryFromVal,
+    Val,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DisputeStatus {
+    Open,
+    Voting,
+    Resolved,
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DisputeResult {
+    UpheldForRaiser,
+    DismissedForRaiser,
+    Tied,
+}
+
+/// The parts of the dispute contract's `Dispute` record a release depends on
+///
+/// Decoded by hand from the record's field map: its `result` field is an
+/// `Option` of a contract type, which a `#[contracttype]` mirror can't hold.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisputeOutcome {
+    pub status: DisputeStatus,
+    pub result: Option<DisputeResult>,
+}
+
+impl TryFromVal<Env, Val> for DisputeOutcome {
+    type Error = ConversionError;
+
+    fn try_from_val(env: &Env, val: &Val) -> Result<Self, Self::Error> {
+        let fields: Map<Symbol, Val> = Map::try_from_val(env, val)?;
+        let status = fields.get(Symbol::new(env, "status")).ok_or(ConversionError)?;
+        let result = fields.get(Symbol::new(env, "result")).ok_or(ConversionError)?;
+
+        Ok(DisputeOutcome {
+            status: DisputeStatus::try_from_val(env, &status)?,
+            result: Option::<DisputeResult>::try_from_val(env, &result)?,
+        })
+    }
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "DisputeClient")]
+pub trait DisputeInterface {
+    /// Open a dispute against a split; returns the new dispute ID
+    fn raise_dispute(env: Env, split_id: String, raiser: Address, reason: String) -> String;
+
+    /// Current state of a dispute
+    fn get_dispute(env: Env, dispute_id: String) -> DisputeOutcome;
+}
@@ -1,7 +1,7 @@
-use soroban_sdk::{symbol_short, Symbol, Address, Env, String};
-
 use soroban_sdk::{symbol_short, Address, Env, String};
 
+use crate::types::{ActivityType, PauseGroup};
+
 /// Emit when the contract is initialized
 ///
 /// I'm emitting this once during contract setup so indexers
@@ -15,10 +15,10 @@ pub fn emit_initialized(env: &Env, admin: &Address) {
 ///
 /// I'm including the key details so the backend can create
 /// a corresponding record without querying the contract.
-pub fn emit_split_created(env: &Env, split_id: u64, creator: &Address, total_amount: i128) {
+pub fn emit_split_created(env: &Env, split_id: &String, creator: &Address, total_amount: i128) {
     env.events().publish(
         (symbol_short!("created"),),
-        (split_id, creator.clone(), total_amount),
+        (split_id.clone(), creator.clone(), total_amount),
     );
 }
 
@@ -26,10 +26,10 @@ pub fn emit_split_created(env: &Env, split_id: u64, creator: &Address, total_amo
 ///
 /// I'm emitting this for each deposit so the backend can
 /// track partial payments and update participant status.
-pub fn emit_deposit_received(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+pub fn emit_deposit_received(env: &Env, split_id: &String, participant: &Address, amount: i128) {
     env.events().publish(
         (symbol_short!("deposit"),),
-        (split_id, participant.clone(), amount),
+        (split_id.clone(), participant.clone(), amount),
     );
 }
 
@@ -37,49 +37,126 @@ pub fn emit_deposit_received(env: &Env, split_id: u64, participant: &Address, am
 ///
 /// Includes the asset address so off-chain indexers can reconcile
 /// which token was used for each participant's payment.
-pub fn emit_payment_received(env: &Env, split_id: u64, participant: &Address, asset: &Address, amount: i128) {
+pub fn emit_payment_received(env: &Env, split_id: &String, participant: &Address, asset: &Address, amount: i128) {
     env.events().publish(
         (symbol_short!("pmtrecv"),),
-        (split_id, participant.clone(), asset.clone(), amount),
+        (split_id.clone(), participant.clone(), asset.clone(), amount),
     );
 }
 
 /// Emit when funds are released to the creator
 ///
-/// I'm including the total amount released for reconciliation
-/// with the backend's payment records.
+/// I'm including the amount received and the protocol fee withheld from
+/// it for reconciliation with the backend's payment records.
 pub fn emit_funds_released(
     env: &Env,
-    split_id: u64,
+    split_id: &String,
     recipient: Address,
     amount: i128,
+    fee: i128,
 ) {
-    let topics = (symbol_short!("funds_rls"), split_id);
-    let data = (recipient, amount, env.ledger().timestamp());
+    let topics = (symbol_short!("funds_rls"), split_id.clone());
+    let data = (recipient, amount, fee, env.ledger().timestamp());
+    env.events().publish(topics, data);
+}
+
+/// Emitted when collected protocol fees are sent to the treasury.
+pub fn emit_fees_withdrawn(env: &Env, asset: Address, treasury: Address, amount: i128) {
+    let topics = (symbol_short!("fee_wdraw"), asset);
+    let data = (treasury, amount, env.ledger().timestamp());
     env.events().publish(topics, data);
 }
 
+/// Emitted when a group of entry points is paused by the admin or guardian.
+pub fn emit_paused(env: &Env, group: PauseGroup, paused_by: Address) {
+    let topics = (symbol_short!("paused"), group);
+    let data = (paused_by, env.ledger().timestamp());
+    env.events().publish(topics, data);
+}
+
+/// Emitted when the admin unpauses a group of entry points.
+pub fn emit_unpaused(env: &Env, group: PauseGroup) {
+    let topics = (symbol_short!("unpaused"), group);
+    env.events().publish(topics, env.ledger().timestamp());
+}
+
 /// Emitted when the creator explicitly cancels the escrow.
-pub fn emit_escrow_cancelled(env: &Env, split_id: u64, cancelled_by: Address) {
-    let topics = (symbol_short!("e_cancel"), split_id);
+pub fn emit_escrow_cancelled(env: &Env, split_id: &String, cancelled_by: Address) {
+    let topics = (symbol_short!("e_cancel"), split_id.clone());
     let data = (cancelled_by, env.ledger().timestamp());
     env.events().publish(topics, data);
 }
 
 /// Emitted when the escrow deadline passes with outstanding unfunded amounts.
-pub fn emit_escrow_expired(env: &Env, split_id: u64, unfunded_amount: i128) {
-    let topics = (symbol_short!("e_expired"), split_id);
+pub fn emit_escrow_expired(env: &Env, split_id: &String, unfunded_amount: i128) {
+    let topics = (symbol_short!("e_expired"), split_id.clone());
     let data = (unfunded_amount, env.ledger().timestamp());
     env.events().publish(topics, data);
 }
 
 /// Emitted when a refund is issued to a participant.
-pub fn emit_refund_issued(env: &Env, split_id: u64, participant: Address, amount: i128) {
-    let topics = (symbol_short!("refund"), split_id);
+pub fn emit_refund_issued(env: &Env, split_id: &String, participant: Address, amount: i128) {
+    let topics = (symbol_short!("refund"), split_id.clone());
     let data = (participant, amount, env.ledger().timestamp());
     env.events().publish(topics, data);
 }
 
+/// Emitted when a recurring escrow closes one cycle and opens the next.
+pub fn emit_cycle_rolled_over(env: &Env, split_id: &String, closed_cycle: u32, next_deadline: Option<u64>) {
+    let topics = (symbol_short!("rollover"), split_id.clone());
+    let data = (closed_cycle, next_deadline, env.ledger().timestamp());
+    env.events().publish(topics, data);
+}
+
+/// Emitted when `collect_due` pulls a participant's share via allowance.
+pub fn emit_auto_collected(env: &Env, split_id: &String, participant: Address, amount: i128) {
+    let topics = (symbol_short!("auto_pull"), split_id.clone());
+    let data = (participant, amount, env.ledger().timestamp());
+    env.events().publish(topics, data);
+}
+
+/// Emitted when `collect_due` fails to pull a participant's share.
+pub fn emit_collection_failed(env: &Env, split_id: &String, participant: Address, amount_due: i128) {
+    let topics = (symbol_short!("pull_fail"), split_id.clone());
+    let data = (participant, amount_due, env.ledger().timestamp());
+    env.events().publish(topics, data);
+}
+
+/// Emitted when the creator proposes a participant amendment.
+pub fn emit_amendment_proposed(env: &Env, split_id: &String, proposer: Address, required_approvals: u32) {
+    let topics = (symbol_short!("amd_prop"), split_id.clone());
+    let data = (proposer, required_approvals, env.ledger().timestamp());
+    env.events().publish(topics, data);
+}
+
+/// Emitted when an amendment has been applied to the escrow.
+pub fn emit_amendment_applied(env: &Env, split_id: &String, new_total: i128) {
+    let topics = (symbol_short!("amd_done"), split_id.clone());
+    let data = (new_total, env.ledger().timestamp());
+    env.events().publish(topics, data);
+}
+
+/// Emitted when an overpayment is held as credit for the payer.
+pub fn emit_credit_added(env: &Env, owner: Address, asset: Address, amount: i128) {
+    let topics = (symbol_short!("credit"), owner);
+    let data = (asset, amount, env.ledger().timestamp());
+    env.events().publish(topics, data);
+}
+
+/// Emitted when credit is withdrawn or spent on a split.
+pub fn emit_credit_used(env: &Env, owner: Address, asset: Address, amount: i128) {
+    let topics = (symbol_short!("cred_use"), owner);
+    let data = (asset, amount, env.ledger().timestamp());
+    env.events().publish(topics, data);
+}
+
+/// Emitted when one address pays toward another participant's share.
+pub fn emit_paid_on_behalf(env: &Env, split_id: &String, payer: Address, beneficiary: Address, amount: i128) {
+    let topics = (symbol_short!("pay_for"), split_id.clone());
+    let data = (payer, beneficiary, amount, env.ledger().timestamp());
+    env.events().publish(topics, data);
+}
+
 // ── Legacy/Compatibility Emitters ───────────────────────────────────────────
 
 /// Emit when an insurance policy is purchased
@@ -103,7 +180,14 @@ pub fn emit_insurance_purchased(
     );
 }
 
-pub fn emit_deposit_received(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+/// Emit when an insurance claim is filed
+pub fn emit_claim_filed(
+    env: &Env,
+    claim_id: &String,
+    insurance_id: &String,
+    claimant: &Address,
+    claim_amount: i128,
+) {
     env.events().publish(
         (symbol_short!("clm_file"),),
         (
@@ -147,11 +231,12 @@ pub fn emit_payout_made(
     );
 }
 
-pub fn emit_activity_tracked(env: &Env, user: &Address, activity_type: &str, split_id: u64, amount: i128) {
+/// Emit when a user's activity is recorded for rewards
+pub fn emit_activity_tracked(env: &Env, user: &Address, activity_type: ActivityType, split_id: &String, amount: i128) {
     env.events()
         .publish(
             (symbol_short!("act_track"),),
-            (user.clone(), activity_type, split_id, amount)
+            (user.clone(), activity_type, split_id.clone(), amount)
         );
 }
 
@@ -173,26 +258,6 @@ pub fn emit_rewards_claimed(env: &Env, user: &Address, amount_claimed: i128) {
 
 // ── Insurance & Verification ────────────────────────────────────────────────
 
-pub fn emit_insurance_purchased(
-    env: &Env,
-    insurance_id: &String,
-    split_id: &String,
-    policy_holder: &Address,
-    premium: i128,
-    coverage_amount: i128,
-) {
-    env.events().publish(
-        (Symbol::new(env, "ins_purchased"),),
-        (
-            insurance_id.clone(),
-            split_id.clone(),
-            policy_holder.clone(),
-            premium,
-            coverage_amount,
-        ),
-    );
-}
-
 pub fn emit_verification_submitted(env: &Env, verification_id: &String, split_id: &String, requester: &Address) {
     env.events()
         .publish(
@@ -201,6 +266,24 @@ pub fn emit_verification_submitted(env: &Env, verification_id: &String, split_id
         );
 }
 
+/// Emit when an oracle attests to a verification request
+pub fn emit_verification_attested(env: &Env, verification_id: &String, oracle: &Address, genuine: bool) {
+    env.events()
+        .publish(
+            (symbol_short!("ver_att"),),
+            (verification_id.clone(), oracle.clone(), genuine)
+        );
+}
+
+/// Emit when a verification request times out without a quorum
+pub fn emit_verification_expired(env: &Env, verification_id: &String) {
+    env.events()
+        .publish(
+            (symbol_short!("ver_exp"),),
+            (verification_id.clone(),)
+        );
+}
+
 /// Emit when verification is completed
 ///
 /// This event is emitted when an oracle processes a verification request.
@@ -220,14 +303,23 @@ pub fn emit_swap_created(env: &Env, swap_id: &String, participant_a: &Address, p
         );
 }
 
+/// Emit when the counterparty escrows their leg of an atomic swap
+pub fn emit_swap_locked(env: &Env, swap_id: &String, participant_b: &Address) {
+    env.events()
+        .publish(
+            (symbol_short!("swp_lock"),),
+            (swap_id.clone(), participant_b.clone())
+        );
+}
+
 /// Emit when atomic swap is executed
 ///
 /// This event is emitted when an atomic swap is successfully completed.
-pub fn emit_swap_executed(env: &Env, swap_id: &String, executor: &Address) {
+pub fn emit_swap_executed(env: &Env, swap_id: &String) {
     env.events()
         .publish(
             (symbol_short!("swp_exec"),),
-            (swap_id.clone(), executor.clone())
+            (swap_id.clone(),)
         );
 }
 
@@ -275,6 +367,24 @@ pub fn emit_consensus_reached(env: &Env, asset_pair: &String, consensus_price: i
         );
 }
 
+/// Emit when an oracle is penalised for an outlier price
+pub fn emit_oracle_slashed(env: &Env, oracle_address: &Address, asset_pair: &String, slashed: i128, reputation: u64) {
+    env.events()
+        .publish(
+            (symbol_short!("orc_slash"),),
+            (oracle_address.clone(), asset_pair.clone(), slashed, reputation)
+        );
+}
+
+/// Emit when an oracle falls below the reputation floor
+pub fn emit_oracle_deactivated(env: &Env, oracle_address: &Address) {
+    env.events()
+        .publish(
+            (symbol_short!("orc_off"),),
+            (oracle_address.clone(),)
+        );
+}
+
 /// Emit when bridge transaction is initiated
 ///
 /// This event is emitted when a cross-chain bridge transaction is started.
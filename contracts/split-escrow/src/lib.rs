@@ -1,8 +1,12 @@
 use soroban_sdk::{
-    contract, contractimpl, Address, Env, String, Vec, token, Map,
+    contract, contractimpl, symbol_short, Address, Bytes, BytesN, Env, String, Vec, token, Map,
     token::Client as TokenClient,
+    xdr::ToXdr,
 };
 
+mod admin;
+mod dispute;
+mod multisig;
 mod storage;
 mod types;
 mod events;
@@ -21,6 +25,9 @@ pub struct SplitEscrowContract;
 impl SplitEscrowContract {
     /// Initialize the contract with an admin and token address
     pub fn initialize(env: Env, admin: Address, token: Address) {
+        if storage::has_admin(&env) {
+            panic!("Contract already initialized");
+        }
         admin.require_auth();
         storage::set_admin(&env, &admin);
 
@@ -32,21 +39,90 @@ impl SplitEscrowContract {
         events::emit_initialized(&env, &admin);
     }
 
-    /// Add a token to the approved-asset allowlist. Admin-only.
+    /// Propose a new admin. The nominee must call `accept_admin` to take over.
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        admin::propose_new_admin(&env, new_admin)?;
+        Ok(())
+    }
+
+    /// Accept a pending admin nomination. Must be called by the nominee.
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        admin::accept_admin(&env)?;
+        Ok(())
+    }
+
+    /// Get the nominee of a pending admin transfer
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        admin::get_pending_admin(&env)
+    }
+
+    /// Grant a role to an account. Admin-only.
+    pub fn grant_role(env: Env, role: types::Role, account: Address) -> Result<(), Error> {
+        admin::grant_role(&env, role, account)?;
+        Ok(())
+    }
+
+    /// Revoke a role from an account. Admin-only.
+    pub fn revoke_role(env: Env, role: types::Role, account: Address) -> Result<(), Error> {
+        admin::revoke_role(&env, role, account)?;
+        Ok(())
+    }
+
+    /// Check whether an account holds a role (the admin holds them all)
+    pub fn has_role(env: Env, role: types::Role, account: Address) -> bool {
+        admin::has_role(&env, role, &account)
+    }
+
+    /// Set the guardian, who can pause but not unpause. Admin-only.
+    pub fn set_guardian(env: Env, guardian: Address) {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_guardian(&env, &guardian);
+    }
+
+    /// Pause a group of entry points
+    ///
+    /// Pausers (including the admin) and the guardian can pause; each
+    /// group is independent, so pausing deposits leaves refunds open.
+    pub fn pause(env: Env, caller: Address, group: types::PauseGroup) {
+        caller.require_auth();
+
+        let is_guardian = storage::get_guardian(&env).is_some_and(|g| g == caller);
+        if !admin::has_role(&env, types::Role::Pauser, &caller) && !is_guardian {
+            panic!("Only a pauser or the guardian can pause");
+        }
+
+        storage::set_paused(&env, group, true);
+        events::emit_paused(&env, group, caller);
+    }
+
+    /// Unpause a group of entry points. Pauser-only.
+    pub fn unpause(env: Env, caller: Address, group: types::PauseGroup) {
+        Self::require_role_internal(&env, &caller, types::Role::Pauser);
+
+        storage::set_paused(&env, group, false);
+        events::emit_unpaused(&env, group);
+    }
+
+    /// Check whether a group of entry points is paused
+    pub fn is_paused(env: Env, group: types::PauseGroup) -> bool {
+        storage::is_paused(&env, group)
+    }
+
+    /// Add a token to the approved-asset allowlist. Asset-manager-only.
     ///
     /// Issue #201: admins must pre-approve every token that participants
     /// are allowed to deposit with. Unapproved assets are rejected at
     /// create_split time.
-    pub fn add_approved_asset(env: Env, asset: Address) {
-        let admin = storage::get_admin(&env);
-        admin.require_auth();
+    pub fn add_approved_asset(env: Env, caller: Address, asset: Address) {
+        Self::require_role_internal(&env, &caller, types::Role::AssetManager);
         storage::set_asset_approved(&env, &asset);
     }
 
-    /// Remove a token from the approved-asset allowlist. Admin-only.
-    pub fn remove_approved_asset(env: Env, asset: Address) {
-        let admin = storage::get_admin(&env);
-        admin.require_auth();
+    /// Remove a token from the approved-asset allowlist. Asset-manager-only.
+    pub fn remove_approved_asset(env: Env, caller: Address, asset: Address) {
+        Self::require_role_internal(&env, &caller, types::Role::AssetManager);
         storage::set_asset_revoked(&env, &asset);
     }
 
@@ -59,6 +135,12 @@ impl SplitEscrowContract {
     ///
     /// Extended in issue #201: each participant now specifies the token they will pay with.
     /// All assets must be on the approved-asset allowlist or the call is rejected.
+    ///
+    /// If `line_items` is non-empty the shares are derived from the items and
+    /// `participant_shares` must be empty; the items are stored on the escrow.
+    ///
+    /// Returns the new escrow's split ID, the decimal form of the split counter.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_split(
         env: Env,
         creator: Address,
@@ -67,14 +149,28 @@ impl SplitEscrowContract {
         participant_addresses: Vec<Address>,
         participant_shares: Vec<i128>,
         participant_assets: Vec<Address>,
-    ) -> u64 {
+        line_items: Vec<types::LineItem>,
+        deadline: u64,
+    ) -> String {
         // Verify the creator is authorizing this call
         creator.require_auth();
 
-        if storage::is_paused(&env) {
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
             panic!("Contract is paused");
         }
 
+        let participant_shares = if line_items.is_empty() {
+            participant_shares
+        } else {
+            if !participant_shares.is_empty() {
+                panic!("Shares are derived from line items and must not be given");
+            }
+            match types::shares_from_line_items(&env, &line_items, &participant_addresses) {
+                Ok(shares) => shares,
+                Err(reason) => panic!("{}", reason),
+            }
+        };
+
         if participant_addresses.len() != participant_shares.len() {
             panic!("Participant addresses and shares must have the same length");
         }
@@ -101,8 +197,13 @@ impl SplitEscrowContract {
             }
         }
 
+        if deadline <= env.ledger().timestamp() {
+            panic!("Deadline must be in the future");
+        }
+
         // Get the next split ID
-        let split_id = storage::get_next_split_id(&env);
+        let split_id_num = storage::get_next_split_id(&env);
+        let split_id = storage::format_number_as_string(&env, split_id_num);
 
         // Create participant entries (now includes per-participant asset)
         let mut participants = Vec::new(&env);
@@ -110,9 +211,11 @@ impl SplitEscrowContract {
             let participant = EscrowParticipant {
                 address: participant_addresses.get(i).unwrap(),
                 asset: participant_assets.get(i).unwrap(),
-                share_amount: participant_shares.get(i).unwrap(),
+                amount_owed: participant_shares.get(i).unwrap(),
                 amount_paid: 0,
                 paid_at: None,
+                auto_collect: false,
+                late_fee: 0,
             };
             participants.push_back(participant);
         }
@@ -126,13 +229,26 @@ impl SplitEscrowContract {
             amount_collected: 0,
             participants,
             status: EscrowStatus::Active,
-            deadline: deadline,
+            deadline,
             created_at: env.ledger().timestamp(),
+            payees: Vec::new(&env),
+            asset_collected: Map::new(&env),
+            asset_released: Map::new(&env),
+            release_schedule: types::ReleaseSchedule::Immediate,
+            recurrence: types::Recurrence::OneOff,
+            deadline_policy: types::DeadlinePolicy::Hard,
+            overpayment_policy: types::OverpaymentPolicy::Reject,
+            line_items,
+            multisig_gated: false,
         };
 
+        if let Err(reason) = escrow.validate() {
+            panic!("{}", reason);
+        }
+
         storage::set_escrow(&env, &split_id, &escrow);
 
-        events::emit_split_created(&env, split_id_num, &creator, total_amount);
+        events::emit_split_created(&env, &split_id, &creator, total_amount);
 
         split_id
     }
@@ -141,671 +257,2950 @@ impl SplitEscrowContract {
     ///
     /// Issue #201: each participant now pays with their own asset.
     /// The correct token client is selected from the participant record.
-    pub fn deposit(env: Env, split_id: u64, participant: Address, amount: i128) {
+    pub fn deposit(env: Env, split_id_str: String, participant: Address, amount: i128) {
         // Verify the participant is authorizing this call
         participant.require_auth();
 
-        if storage::is_paused(&env) {
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
             panic!("Contract is paused");
         }
 
         let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        let (asset_address, accepted) = Self::record_payment_internal(&env, &mut escrow, &participant, amount);
 
-        if escrow.status == EscrowStatus::Active && env.ledger().timestamp() > escrow.deadline {
-            escrow.status = EscrowStatus::Expired;
-        }
-
-        if amount <= 0 {
-            panic!("Deposit amount must be positive");
-        }
-
-        if escrow.status == EscrowStatus::Expired {
-            panic!("Escrow has expired");
-        }
-
-        if escrow.status != EscrowStatus::Active {
-            panic!("Escrow is not active");
-        }
-
-        // Find the participant in the split and capture their asset
-        let mut found = false;
-        let mut participant_asset: Option<Address> = None;
-        let mut updated_participants = Vec::new(&env);
-
-        for i in 0..escrow.participants.len() {
-            let mut p = escrow.participants.get(i).unwrap();
-            if p.address == participant {
-                found = true;
-                let remaining = p.amount_owed - p.amount_paid;
-                if amount > remaining {
-                    panic!("Deposit exceeds remaining amount owed");
-                }
-                // Capture this participant's asset for transfer
-                participant_asset = Some(p.asset.clone());
-
-                p.amount_paid += amount;
-                if p.amount_paid >= p.amount_owed {
-                    p.paid_at = Some(env.ledger().timestamp());
-                }
-            }
-            updated_participants.push_back(p);
-        }
-
-        if !found {
-            panic!("Participant not found in escrow");
-        }
-
-        // Transfer tokens from participant to escrow using the participant's own asset
-        let asset_address = participant_asset.expect("Asset not set");
+        // Transfer tokens from participant to escrow using the participant's own asset.
+        // Under the Credit policy the whole amount is taken and the excess held.
+        let excess = if escrow.overpayment_policy == types::OverpaymentPolicy::Credit {
+            amount - accepted
+        } else {
+            0
+        };
         let token_client = token::Client::new(&env, &asset_address);
         let contract_address = env.current_contract_address();
-        token_client.transfer(&participant, &contract_address, &amount);
+        token_client.transfer(&participant, &contract_address, &(accepted + excess));
 
-        escrow.participants = updated_participants;
-        escrow.amount_collected += amount;
-
-        // Transition from Pending → Active on first deposit
-        if split.status == SplitStatus::Pending {
-            split.status = SplitStatus::Active;
+        if excess > 0 {
+            storage::add_credit(&env, &participant, &asset_address, excess);
+            events::emit_credit_added(&env, participant.clone(), asset_address.clone(), excess);
         }
 
+        Self::log_payment_internal(&env, &split_id_str, &participant, &participant, accepted);
         storage::set_escrow(&env, &split_id_str, &escrow);
 
         // Emit multi-asset PaymentReceived event (issue #201)
-        events::emit_payment_received(&env, split_id, &participant, &asset_address, amount);
+        events::emit_payment_received(&env, &split_id_str, &participant, &asset_address, accepted);
         // Also emit legacy deposit event for backwards compatibility
-        events::emit_deposit_received(&env, split_id, &participant, amount);
+        events::emit_deposit_received(&env, &split_id_str, &participant, accepted);
 
-        if escrow.is_fully_funded() {
-            let _ = Self::release_funds_internal(&env, split_id_str, escrow);
-        }
+        Self::settle_if_funded_internal(&env, split_id_str, escrow);
     }
 
-    /// Release funds from a completed split to the creator
-    pub fn release_funds(env: Env, split_id_str: String) -> Result<(), Error> {
-        if storage::is_paused(&env) {
+    /// Pay toward a split using overpayment credit held by this contract
+    ///
+    /// The credit must be in the participant's asset for this split.
+    pub fn deposit_from_credit(env: Env, split_id_str: String, participant: Address, amount: i128) {
+        participant.require_auth();
+
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
             panic!("Contract is paused");
         }
 
-        if !storage::has_escrow(&env, &split_id_str) {
-            return Err(Error::SplitNotFound);
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        let (asset_address, accepted) = Self::record_payment_internal(&env, &mut escrow, &participant, amount);
+
+        let balance = storage::get_credit(&env, &participant, &asset_address);
+        if balance < accepted {
+            panic!("Insufficient credit");
         }
+        storage::set_credit(&env, &participant, &asset_address, balance - accepted);
+        events::emit_credit_used(&env, participant.clone(), asset_address.clone(), accepted);
+
+        Self::log_payment_internal(&env, &split_id_str, &participant, &participant, accepted);
+        storage::set_escrow(&env, &split_id_str, &escrow);
+
+        events::emit_payment_received(&env, &split_id_str, &participant, &asset_address, accepted);
+        events::emit_deposit_received(&env, &split_id_str, &participant, accepted);
 
-        let split = storage::get_split(&env, split_id);
-        Ok(Self::is_fully_funded_internal(&split))
+        Self::settle_if_funded_internal(&env, split_id_str, escrow);
     }
 
-    /// Cancel a split and mark for refunds
+    /// Pay toward another participant's share
     ///
-    /// I'm allowing only the creator to cancel, and only if not fully completed.
-    pub fn cancel_split(env: Env, split_id: u64) {
-        let mut split = storage::get_split(&env, split_id);
+    /// Tokens come from `payer` and count toward `beneficiary`'s share.
+    /// The payment log records who paid, so refunds go back to `payer`
+    /// and any overpayment credit is held for `payer`.
+    pub fn deposit_for(
+        env: Env,
+        split_id_str: String,
+        payer: Address,
+        beneficiary: Address,
+        amount: i128,
+    ) {
+        payer.require_auth();
+
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
+            panic!("Contract is paused");
+        }
 
-        // Only the creator can cancel
-        split.creator.require_auth();
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        let (asset_address, accepted) = Self::record_payment_internal(&env, &mut escrow, &beneficiary, amount);
+
+        let excess = if escrow.overpayment_policy == types::OverpaymentPolicy::Credit {
+            amount - accepted
+        } else {
+            0
+        };
+        let token_client = token::Client::new(&env, &asset_address);
+        token_client.transfer(&payer, &env.current_contract_address(), &(accepted + excess));
 
-        // Can't cancel a completed split that's been released
-        if split.status == SplitStatus::Released {
-            panic!("Cannot cancel a released split");
+        if excess > 0 {
+            storage::add_credit(&env, &payer, &asset_address, excess);
+            events::emit_credit_added(&env, payer.clone(), asset_address.clone(), excess);
         }
 
-        // Mark as cancelled
-        split.status = SplitStatus::Cancelled;
-        storage::set_split(&env, split_id, &split);
+        Self::log_payment_internal(&env, &split_id_str, &payer, &beneficiary, accepted);
+        storage::set_escrow(&env, &split_id_str, &escrow);
 
-        // Emit cancellation event
-        events::emit_split_cancelled(&env, split_id);
-    }
+        events::emit_payment_received(&env, &split_id_str, &beneficiary, &asset_address, accepted);
+        events::emit_paid_on_behalf(&env, &split_id_str, payer, beneficiary, accepted);
 
-    /// Get split details by ID
-    pub fn get_split(env: Env, split_id: u64) -> Split {
-        storage::get_split(&env, split_id)
+        Self::settle_if_funded_internal(&env, split_id_str, escrow);
     }
 
-    /// Get the contract admin
-    pub fn get_admin(env: Env) -> Address {
-        storage::get_admin(&env)
+    /// Get who paid what toward each participant's share of an escrow
+    pub fn get_payment_log(env: Env, split_id_str: String) -> Vec<types::PaymentRecord> {
+        storage::get_payment_log(&env, &split_id_str)
     }
 
-    /// Get the token contract address
-    pub fn get_token(env: Env) -> Address {
-        storage::get_token(&env)
-    }
+    /// Withdraw overpayment credit held in `asset`
+    ///
+    /// Returns the amount withdrawn.
+    pub fn withdraw_credit(env: Env, owner: Address, asset: Address, amount: i128) -> i128 {
+        owner.require_auth();
 
-    // ============================================
-    // Insurance Query Functions
-    // ============================================
+        if amount <= 0 {
+            panic!("Withdrawal amount must be positive");
+        }
 
-    /// Get insurance policy by ID
-    pub fn get_insurance(env: Env, insurance_id: String) -> types::InsurancePolicy {
-        storage::get_insurance(&env, &insurance_id)
-    }
+        let balance = storage::get_credit(&env, &owner, &asset);
+        if balance < amount {
+            panic!("Insufficient credit");
+        }
 
-    /// Get insurance claim by ID
-    pub fn get_claim(env: Env, claim_id: String) -> types::InsuranceClaim {
-        storage::get_claim(&env, &claim_id)
-    }
+        storage::set_credit(&env, &owner, &asset, balance - amount);
 
-    /// Get all claims for an insurance policy
-    pub fn get_insurance_claims(env: Env, insurance_id: String) -> Vec<String> {
-        storage::get_insurance_claims(&env, &insurance_id)
-    }
+        let token_client = TokenClient::new(&env, &asset);
+        token_client.transfer(&env.current_contract_address(), &owner, &amount);
 
-    /// Check if a split has insurance
-    pub fn has_split_insurance(env: Env, split_id: String) -> bool {
-        storage::has_split_insurance(&env, &split_id)
+        events::emit_credit_used(&env, owner, asset, amount);
+
+        amount
     }
 
-    /// Get insurance ID for a split
-    pub fn get_split_insurance(env: Env, split_id: u64) -> Option<String> {
-        storage::get_split_to_insurance(&env, &String::from_str(&env, "123"))
+    /// Get a user's overpayment credit in `asset`
+    pub fn get_credit(env: Env, owner: Address, asset: Address) -> i128 {
+        storage::get_credit(&env, &owner, &asset)
     }
 
-    /// Track user split usage for rewards calculation
+    /// Choose how this escrow handles deposits above what's owed
     ///
-    /// This function records user activities that contribute to rewards.
-    pub fn track_split_usage(
-        env: Env,
-        user: Address,
-    ) -> Result<(), Error> {
-        // Get caller's address (require_auth for the caller)
-        let caller = env.current_contract_address();
-        caller.require_auth();
-
-        // Get or create user rewards data
-        let mut rewards = if let Some(existing_rewards) = storage::get_user_rewards(&env, &user) {
-            existing_rewards
-        } else {
-            types::UserRewards {
-                user: user.clone(),
-                total_splits_created: 0,
-                total_splits_participated: 0,
-                total_amount_transacted: 0,
-                rewards_earned: 0,
-                rewards_claimed: 0,
-                last_activity: env.ledger().timestamp(),
-                status: types::RewardsStatus::Active,
-            }
-        };
-
-        // Create activity record
-        let activity_id = storage::get_next_activity_id(&env);
-        let activity = types::UserActivity {
-            user: user.clone(),
-            activity_type: types::ActivityType::SplitParticipated,
-            split_id: 0, // This would be set based on context
-            amount: 0, // This would be set based on context
-            timestamp: env.ledger().timestamp(),
-        };
-
-        // Store activity
-        storage::set_user_activity(&env, &user, activity_id, &activity);
+    /// Only the creator can set this, and only before any deposit.
+    pub fn set_overpayment_policy(env: Env, split_id_str: String, policy: types::OverpaymentPolicy) {
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        escrow.creator.require_auth();
 
-        // Update rewards data
-        rewards.total_splits_participated += 1;
-        rewards.last_activity = env.ledger().timestamp();
-        
-        // Store updated rewards
-        storage::set_user_rewards(&env, &user, &rewards);
+        if escrow.status != EscrowStatus::Active {
+            panic!("Escrow is not active");
+        }
 
-        // Emit activity tracked event
-        events::emit_activity_tracked(&env, &user, "split_participated", 0, 0);
+        if escrow.amount_collected > 0 {
+            panic!("Overpayment policy cannot change after deposits");
+        }
 
-        Ok(())
+        escrow.overpayment_policy = policy;
+        storage::set_escrow(&env, &split_id_str, &escrow);
     }
 
-    /// Calculate rewards for a user
+    /// Opt in or out of allowance-based auto-collection
     ///
-    /// This function calculates the total rewards earned by a user based on their activity.
-    pub fn calculate_rewards(
-        env: Env,
-        user: Address,
-    ) -> i128 {
-        // Get user rewards data
-        let rewards = storage::get_user_rewards(&env, &user)
-            .unwrap_or(types::UserRewards {
-                user: user.clone(),
-                total_splits_created: 0,
-                total_splits_participated: 0,
-                total_amount_transacted: 0,
-                rewards_earned: 0,
-                rewards_claimed: 0,
-                last_activity: env.ledger().timestamp(),
-                status: types::RewardsStatus::Active,
-            });
+    /// The participant must also `approve` this contract on their asset's
+    /// token for at least their share; `collect_due` then pulls it for them.
+    pub fn set_auto_collect(env: Env, split_id_str: String, participant: Address, enabled: bool) {
+        participant.require_auth();
 
-        // Calculate rewards based on activity
-        // Base rewards: 10 tokens per split created
-        let creation_rewards = rewards.total_splits_created as i128 * 10;
-        
-        // Participation rewards: 5 tokens per split participated
-        let participation_rewards = rewards.total_splits_participated as i128 * 5;
-        
-        // Volume rewards: 0.1% of total amount transacted
-        let volume_rewards = rewards.total_amount_transacted / 1000;
-        
-        // Total rewards
-        let total_rewards = creation_rewards + participation_rewards + volume_rewards;
-        
-        // Update rewards earned
-        let rewards_claimed = rewards.rewards_claimed;
-        let mut updated_rewards = rewards;
-        updated_rewards.rewards_earned = total_rewards;
-        storage::set_user_rewards(&env, &user, &updated_rewards);
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
 
-        // Calculate available rewards (earned - claimed)
-        let available_rewards = total_rewards - rewards_claimed;
+        let mut found = false;
+        let mut updated_participants = Vec::new(&env);
+        for i in 0..escrow.participants.len() {
+            let mut p = escrow.participants.get(i).unwrap();
+            if p.address == participant {
+                found = true;
+                p.auto_collect = enabled;
+            }
+            updated_participants.push_back(p);
+        }
 
-        // Emit rewards calculated event
-        events::emit_rewards_calculated(&env, &user, total_rewards, available_rewards);
+        if !found {
+            panic!("Participant not found in escrow");
+        }
 
-        total_rewards
+        escrow.participants = updated_participants;
+        storage::set_escrow(&env, &split_id_str, &escrow);
     }
 
-    /// Claim a refund for a cancelled or expired split
-    pub fn claim_refund(env: Env, split_id_str: String, participant: Address) -> Result<i128, Error> {
-        if storage::is_paused(&env) {
+    /// Pull every opted-in participant's outstanding share via `transfer_from`
+    ///
+    /// Anyone can call this while the escrow is active, from
+    /// `COLLECTION_WINDOW` before the deadline onwards. A participant whose
+    /// allowance or balance is short, or whose transfer is rejected, is
+    /// skipped and the failure recorded instead of reverting the batch; any
+    /// late surcharge is only charged when their collection succeeds.
+    /// Returns the total amount collected.
+    pub fn collect_due(env: Env, split_id_str: String) -> Result<i128, Error> {
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
             panic!("Contract is paused");
         }
 
-        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        let mut escrow = storage::get_escrow(&env, &split_id_str).ok_or(Error::SplitNotFound)?;
 
-        // Check if escrow is in a refundable state
-        if escrow.status == EscrowStatus::Active && env.ledger().timestamp() > escrow.deadline {
-            escrow.status = EscrowStatus::Expired;
-            storage::set_escrow(&env, &split_id_str, &escrow);
+        if escrow.status != EscrowStatus::Active || escrow.is_expired(env.ledger().timestamp()) {
+            panic!("Escrow is not active");
         }
 
-        if escrow.status != EscrowStatus::Cancelled && escrow.status != EscrowStatus::Expired {
-            return Err(Error::EscrowNotRefundable);
+        let now = env.ledger().timestamp();
+        if now < escrow.collection_due_at() {
+            return Err(Error::CollectionNotDue);
         }
 
-        // Update claimed rewards
-        let old_claimed = rewards.rewards_claimed;
-        rewards.rewards_claimed += available_rewards;
-        rewards.last_activity = env.ledger().timestamp();
-        
-        // Store updated rewards
-        storage::set_user_rewards(&env, &user, &rewards);
+        let contract_address = env.current_contract_address();
+        let mut total_collected: i128 = 0;
+        let mut updated_participants = Vec::new(&env);
+
+        for i in 0..escrow.participants.len() {
+            let mut p = escrow.participants.get(i).unwrap();
+
+            // Price the surcharge on a copy; it only sticks if the pull succeeds
+            let mut charged = p.clone();
+            let surcharge = escrow.apply_late_surcharge(&mut charged, now);
+            let amount_due = charged.remaining_owed();
+
+            if p.auto_collect && amount_due > 0 {
+                let token_client = TokenClient::new(&env, &p.asset);
+
+                let failure = if token_client.allowance(&p.address, &contract_address) < amount_due {
+                    Some(types::CollectionFailureReason::InsufficientAllowance)
+                } else if token_client.balance(&p.address) < amount_due {
+                    Some(types::CollectionFailureReason::InsufficientBalance)
+                } else if token_client
+                    .try_transfer_from(&contract_address, &p.address, &contract_address, &amount_due)
+                    .is_err()
+                {
+                    Some(types::CollectionFailureReason::TransferFailed)
+                } else {
+                    None
+                };
+
+                match failure {
+                    Some(reason) => {
+                        let record = types::CollectionFailure {
+                            participant: p.address.clone(),
+                            amount_due,
+                            reason,
+                            attempted_at: now,
+                        };
+                        storage::set_collection_failure(&env, &split_id_str, &record);
+                        events::emit_collection_failed(&env, &split_id_str, p.address.clone(), amount_due);
+                    }
+                    None => {
+                        p = charged;
+                        escrow.total_amount += surcharge;
+                        p.amount_paid += amount_due;
+                        p.paid_at = Some(now);
+                        escrow.amount_collected += amount_due;
+                        escrow.record_asset_deposit(&p.asset, amount_due);
+                        total_collected += amount_due;
+
+                        storage::remove_collection_failure(&env, &split_id_str, &p.address);
+                        Self::log_payment_internal(&env, &split_id_str, &p.address, &p.address, amount_due);
+                        events::emit_auto_collected(&env, &split_id_str, p.address.clone(), amount_due);
+                    }
+                }
+            }
+            updated_participants.push_back(p);
+        }
 
-        // Note: In a real implementation, you would transfer tokens here
-        // For now, we'll just emit the event
+        escrow.participants = updated_participants;
+        storage::set_escrow(&env, &split_id_str, &escrow);
 
-        // Emit rewards claimed event
-        events::emit_rewards_claimed(&env, &user, rewards.rewards_claimed - old_claimed);
+        Self::settle_if_funded_internal(&env, split_id_str, escrow);
 
-        Ok(available_rewards)
+        Ok(total_collected)
     }
 
-    /// Submit verification for a split
+    /// Propose a new participant/share set for a live escrow
     ///
-    /// This function allows users to submit verification requests with evidence.
-    pub fn submit_verification(
+    /// Replaces any pending proposal. Every affected participant must call
+    /// `approve_amendment`; if nobody is affected it applies immediately.
+    pub fn propose_amendment(
         env: Env,
-        split_id: String,
-        receipt_hash: String,
-    ) -> Result<String, Error> {
-        // Get caller's address (require_auth for the caller)
-        let caller = env.current_contract_address();
-        caller.require_auth();
+        split_id_str: String,
+        shares: Vec<types::ParticipantShare>,
+    ) -> Vec<Address> {
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
+            panic!("Contract is paused");
+        }
 
-        // Check if split exists
-        let split_id_num = 123; // Simplified for testing
+        let escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        escrow.creator.require_auth();
 
-        for i in 0..escrow.participants.len() {
-            let mut p = escrow.participants.get(i).unwrap();
-            if p.address == participant {
-                found = true;
-                participant.require_auth();
-                
-                if p.amount_paid <= 0 {
-                    return Err(Error::NoFundsAvailable);
-                }
+        if escrow.status != EscrowStatus::Active || escrow.is_expired(env.ledger().timestamp()) {
+            panic!("Escrow is not active");
+        }
 
-                refund_amount = p.amount_paid;
-                p.amount_paid = 0;
-                p.paid_at = None;
-            }
-            updated_participants.push_back(p);
+        if !escrow.asset_released.is_empty() {
+            panic!("Cannot amend after funds have been released");
         }
 
-        // Generate verification ID
-        let verification_id = storage::get_next_verification_id(&env);
+        if shares.is_empty() {
+            panic!("At least one participant is required");
+        }
 
-        // Create verification request
-        let request = types::VerificationRequest {
-            verification_id: verification_id.clone(),
-            split_id: split_id.clone(),
-            requester: caller.clone(),
-            receipt_hash: receipt_hash.clone(),
-            evidence_url: None,
-            submitted_at: env.ledger().timestamp(),
-            status: types::VerificationStatus::Pending,
-            verified_by: None,
-            verified_at: None,
-            rejection_reason: None,
+        for i in 0..shares.len() {
+            let share = shares.get(i).unwrap();
+            if share.amount_owed <= 0 {
+                panic!("Participant shares must be positive");
+            }
+            if !storage::is_asset_approved(&env, &share.asset) {
+                panic!("Asset not approved");
+            }
+        }
+
+        let required_approvals = escrow.affected_by(&env, &shares);
+        let amendment = types::Amendment {
+            proposer: escrow.creator.clone(),
+            shares,
+            required_approvals: required_approvals.clone(),
+            approvals: Vec::new(&env),
+            proposed_at: env.ledger().timestamp(),
         };
 
-        // Store verification request
-        storage::set_verification_request(&env, &verification_id, &request);
+        events::emit_amendment_proposed(
+            &env,
+            &split_id_str,
+            escrow.creator.clone(),
+            required_approvals.len(),
+        );
 
-        // Emit verification submitted event
-        events::emit_verification_submitted(&env, &verification_id, &split_id, &caller);
+        if amendment.is_fully_approved() {
+            Self::apply_amendment_internal(&env, split_id_str, escrow, amendment);
+        } else {
+            storage::set_amendment(&env, &split_id_str, &amendment);
+        }
 
-        Ok(verification_id)
+        required_approvals
     }
 
-    /// Verify a split
+    /// Co-sign the pending amendment; applies it once everyone has signed
     ///
-    /// This function allows authorized oracles to verify split legitimacy.
-    pub fn verify_split(
-        env: Env,
-        verification_id: String,
-        verified: bool,
-    ) -> Result<(), Error> {
-        // Get caller's address (require_auth for the caller)
-        let caller = env.current_contract_address();
-        caller.require_auth();
+    /// Returns true if this approval caused the amendment to be applied.
+    pub fn approve_amendment(env: Env, split_id_str: String, participant: Address) -> bool {
+        participant.require_auth();
 
-        // Get verification request
-        let mut request = storage::get_verification_request(&env, &verification_id)
-            .ok_or(Error::VerificationNotFound)?;
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
+            panic!("Contract is paused");
+        }
 
-        // Check if caller is authorized oracle
-        let oracle_config = storage::get_oracle_config(&env)
-            .ok_or(Error::OracleNotAuthorized)?;
-        
-        if !oracle_config.oracle_addresses.contains(&caller) {
-            return Err(Error::OracleNotAuthorized);
+        let escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        let mut amendment = storage::get_amendment(&env, &split_id_str).expect("No pending amendment");
+
+        if !amendment.required_approvals.contains(&participant) {
+            panic!("Participant is not affected by this amendment");
         }
 
-        if refund_amount <= 0 {
-             return Err(Error::NoFundsAvailable);
+        if escrow.status != EscrowStatus::Active || escrow.is_expired(env.ledger().timestamp()) {
+            panic!("Escrow is not active");
         }
 
-        // Update verification request
-        request.status = if verified {
-            types::VerificationStatus::Verified
-        } else {
-            types::VerificationStatus::Rejected
-        };
-        request.verified_by = Some(caller.clone());
-        request.verified_at = Some(env.ledger().timestamp());
+        if !amendment.approvals.contains(&participant) {
+            amendment.approvals.push_back(participant);
+        }
 
-        if !verified {
-            request.rejection_reason = Some(String::from_str(&env, "Evidence insufficient"));
+        if !amendment.is_fully_approved() {
+            storage::set_amendment(&env, &split_id_str, &amendment);
+            return false;
         }
 
-        escrow.participants = updated_participants;
-        escrow.amount_collected -= refund_amount;
-        storage::set_escrow(&env, &split_id_str, &escrow);
+        storage::remove_amendment(&env, &split_id_str);
+        Self::apply_amendment_internal(&env, split_id_str, escrow, amendment);
+        true
+    }
 
-        events::emit_refund_issued(&env, 0, participant, refund_amount);
+    /// Get the pending amendment for an escrow, if any
+    pub fn get_amendment(env: Env, split_id_str: String) -> Option<types::Amendment> {
+        storage::get_amendment(&env, &split_id_str)
+    }
 
-        Ok(refund_amount)
+    /// Get the latest failed auto-collection for a participant, if any
+    pub fn get_collection_failure(
+        env: Env,
+        split_id_str: String,
+        participant: Address,
+    ) -> Option<types::CollectionFailure> {
+        storage::get_collection_failure(&env, &split_id_str, &participant)
     }
 
-    /// Cancel a split and mark for refunds
-    pub fn cancel_split(env: Env, split_id_str: String) {
-        if storage::is_paused(&env) {
+    /// Release funds from a completed split to the creator
+    pub fn release_funds(env: Env, split_id_str: String) -> Result<(), Error> {
+        if storage::is_paused(&env, types::PauseGroup::Releases) {
             panic!("Contract is paused");
         }
 
-        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
-        escrow.creator.require_auth();
+        if !storage::has_escrow(&env, &split_id_str) {
+            return Err(Error::SplitNotFound);
+        }
 
-        if escrow.status == EscrowStatus::Completed {
-            panic!("Cannot cancel a completed escrow");
+        let escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        if escrow.vesting().is_some() {
+            panic!("Vesting escrows release through claim_vested");
         }
 
-        escrow.status = EscrowStatus::Cancelled;
-        storage::set_escrow(&env, &split_id_str, &escrow);
+        if !escrow.is_fully_funded() {
+            return Err(Error::SplitNotFunded);
+        }
 
-        events::emit_split_cancelled_legacy(&env, 0);
+        Self::release_funds_internal(&env, split_id_str, escrow)?;
+        Ok(())
     }
 
-    pub fn release_partial(env: Env, split_id_str: String) -> Result<i128, Error> {
-        if storage::is_paused(&env) {
-            panic!("Contract is paused");
-        }
+    /// Configure a streaming release schedule for an escrow
+    ///
+    /// Only the creator can set this, and only before any deposit. Once
+    /// set, collected funds vest linearly and are paid out by `claim_vested`
+    /// instead of being released in one go.
+    pub fn set_vesting(env: Env, split_id_str: String, schedule: types::VestingSchedule) {
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        escrow.creator.require_auth();
 
-        if !storage::has_escrow(&env, &split_id_str) {
-            return Err(Error::SplitNotFound);
+        if escrow.status != EscrowStatus::Active {
+            panic!("Escrow is not active");
         }
 
-        let escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
-
-        if escrow.status == EscrowStatus::Cancelled {
-            return Err(Error::SplitCancelled);
+        if escrow.amount_collected > 0 {
+            panic!("Vesting cannot change after deposits");
         }
 
-        let available = escrow.amount_collected;
-        if available <= 0 {
-            return Err(Error::NoFundsAvailable);
+        if !escrow.payees.is_empty() {
+            panic!("Vesting is not supported with payees");
         }
 
-        let token_address = storage::get_token(&env);
-        let token_client = TokenClient::new(&env, &token_address);
-        token_client.transfer(&env.current_contract_address(), &escrow.creator, &available);
-
-        Ok(available)
-    }
+        if escrow.late_policy().is_some() {
+            panic!("Late penalties are not supported with vesting");
+        }
 
-    pub fn is_fully_funded(env: Env, split_id_str: String) -> Result<bool, Error> {
-        if !storage::has_escrow(&env, &split_id_str) {
-            return Err(Error::SplitNotFound);
+        if let Err(reason) = schedule.validate() {
+            panic!("{}", reason);
         }
 
-        let escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
-        Ok(escrow.is_fully_funded())
+        escrow.release_schedule = types::ReleaseSchedule::Vesting(schedule);
+        storage::set_escrow(&env, &split_id_str, &escrow);
     }
 
-    /// Extend escrow deadline
-    pub fn extend_deadline(env: Env, split_id_str: String, new_deadline: u64) {
-        if storage::is_paused(&env) {
-            panic!("Contract is paused");
-        }
-
-        // Create price submission
-        let submission = types::PriceSubmission {
-            oracle_address: oracle.clone(),
-            asset_pair: asset_pair.clone(),
-            price,
-            timestamp: env.ledger().timestamp(),
-            signature: String::from_str(&env, "signature"), // Simplified
-        };
+    /// Configure a grace period and late-payment penalty for an escrow
+    ///
+    /// Only the creator can set this, and only before any deposit.
+    /// Penalties are paid to `policy.treasury` (or the creator) on release.
+    pub fn set_late_policy(env: Env, split_id_str: String, policy: types::LatePolicy) {
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        escrow.creator.require_auth();
 
-        // Store submission
-        storage::set_price_submission(&env, &asset_pair, &oracle, &submission);
+        if escrow.status != EscrowStatus::Active {
+            panic!("Escrow is not active");
+        }
 
-        // Update oracle node
-        oracle_node.submissions_count += 1;
-        oracle_node.last_submission = env.ledger().timestamp();
-        storage::set_oracle_node(&env, &oracle, &oracle_node);
+        if escrow.amount_collected > 0 {
+            panic!("Late policy cannot change after deposits");
+        }
 
-        // Calculate consensus price
-        Self::calculate_consensus_price_internal(&env, &asset_pair);
+        if escrow.vesting().is_some() {
+            panic!("Late penalties are not supported with vesting");
+        }
 
-        // Emit price submitted event
-        events::emit_price_submitted(&env, &oracle, &asset_pair, price);
+        if let Err(reason) = policy.validate() {
+            panic!("{}", reason);
+        }
 
-        Ok(())
+        escrow.deadline_policy = types::DeadlinePolicy::Grace(policy);
+        storage::set_escrow(&env, &split_id_str, &escrow);
     }
 
-    /// Calculate consensus price from oracle submissions
+    /// Make an escrow recur every `interval` seconds for `max_cycles` periods
     ///
-    /// This internal function aggregates oracle submissions and calculates consensus.
-    fn calculate_consensus_price_internal(env: &Env, asset_pair: &String) {
-        // In a real implementation, this would collect all oracle submissions
-        // and apply consensus mechanisms like median, weighted average, etc.
-        // For now, we'll use a simplified approach.
-
-        // Create a mock consensus price
-        let consensus_price = types::ConsensusPrice {
-            asset_pair: asset_pair.clone(),
-            price: 1000, // Mock price
-            confidence: 9500, // 95.00% scaled x100
-            participating_oracles: 3,
-            timestamp: env.ledger().timestamp(),
-        };
+    /// Only the creator can set this, and only before any deposit. Each
+    /// period is closed and the next one opened by `roll_over`.
+    pub fn set_recurrence(env: Env, split_id_str: String, interval: u64, max_cycles: u32) {
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        escrow.creator.require_auth();
 
-        // Store consensus price
-        storage::set_consensus_price(&env, asset_pair, &consensus_price);
+        if escrow.status != EscrowStatus::Active {
+            panic!("Escrow is not active");
+        }
 
-        // Emit consensus reached event
-        events::emit_consensus_reached(&env, asset_pair, consensus_price.price, consensus_price.confidence, consensus_price.participating_oracles);
-    }
+        if escrow.amount_collected > 0 {
+            panic!("Recurrence cannot change after deposits");
+        }
 
-    /// Get consensus price for asset pair
-    ///
-    /// This function returns the consensus price from the oracle network.
-    pub fn get_consensus_price(
-        env: Env,
-        asset_pair: String,
-    ) -> Result<i128, Error> {
-        // Get consensus price
-        let consensus = storage::get_consensus_price(&env, &asset_pair)
-            .ok_or(Error::PriceSubmissionInvalid)?;
+        if escrow.vesting().is_some() {
+            panic!("Recurrence is not supported with vesting");
+        }
 
-        if new_deadline <= escrow.deadline {
-            panic!("New deadline must be later than current");
+        if escrow.multisig_gated {
+            panic!("Recurrence is not supported with a multisig gate");
         }
 
-        if escrow.status != EscrowStatus::Active {
-            panic!("Escrow is not active");
+        if interval == 0 || max_cycles == 0 {
+            panic!("Interval and max cycles must be positive");
         }
 
-        escrow.deadline = new_deadline;
+        escrow.recurrence = types::Recurrence::Recurring(types::RecurrenceConfig {
+            interval,
+            max_cycles,
+            current_cycle: 0,
+        });
         storage::set_escrow(&env, &split_id_str, &escrow);
     }
 
-    /// Complete cross-chain bridge transaction
+    /// Close the current period of a recurring escrow and open the next one
     ///
-    /// This function completes a bridge transaction with proof of destination transaction.
-    pub fn complete_bridge(
-        env: Env,
-        bridge_id: String,
-        _proof: soroban_sdk::Bytes,
-    ) -> Result<(), Error> {
-        // Get caller's address (require_auth for the caller)
-        let caller = env.current_contract_address();
-        caller.require_auth();
+    /// Anyone can call this once the current deadline has passed. A funded
+    /// period is released if it hasn't been already; an underfunded one is
+    /// marked Expired and whatever was paid is refunded. The closed period
+    /// is recorded in the escrow's cycle history.
+    pub fn roll_over(env: Env, split_id_str: String) -> Result<u32, Error> {
+        if storage::is_paused(&env, types::PauseGroup::Releases) {
+            panic!("Contract is paused");
+        }
 
-        // Get bridge transaction
-        let mut bridge = storage::get_bridge_transaction(&env, &bridge_id)
-            .ok_or(Error::BridgeNotFound)?;
+        let mut escrow = storage::get_escrow(&env, &split_id_str).ok_or(Error::SplitNotFound)?;
+        let recurrence = escrow.recurrence_config().expect("Escrow is not recurring");
 
-        // Check if bridge is still initiated
-        if bridge.status != types::BridgeStatus::Initiated {
-            return Err(Error::InvalidBridgeStatus);
+        if escrow.status == EscrowStatus::Cancelled {
+            return Err(Error::SplitCancelled);
         }
 
-        // Validate proof (simplified - in production would use proper verification)
-        if _proof.len() == 0 {
-            return Err(Error::ProofInvalid);
+        if env.ledger().timestamp() <= escrow.payment_cutoff() {
+            panic!("Current cycle has not ended");
         }
 
-        // Update bridge transaction
-        bridge.status = types::BridgeStatus::Completed;
-        bridge.proof_hash = Some(String::from_str(&env, "proof_hash")); // Simplified
-        bridge.completed_at = Some(env.ledger().timestamp());
+        // Already closed its final cycle
+        if !escrow.has_next_cycle() && storage::get_cycle_record(&env, &split_id_str, recurrence.current_cycle).is_some() {
+            panic!("No cycles remaining");
+        }
 
-        // Store updated bridge transaction
-        storage::set_bridge_transaction(&env, &bridge_id, &bridge);
+        if escrow.status == EscrowStatus::Active && escrow.is_fully_funded() {
+            escrow.status = EscrowStatus::Completed;
+        }
+
+        let amount_collected = escrow.amount_collected;
+        let closed_status = match escrow.status {
+            EscrowStatus::Released => EscrowStatus::Released,
+            EscrowStatus::Completed => {
+                Self::release_funds_internal(&env, split_id_str.clone(), escrow.clone())?;
+                escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+                EscrowStatus::Released
+            }
+            _ => {
+                Self::refund_all_internal(&env, &mut escrow);
+                EscrowStatus::Expired
+            }
+        };
 
-        // Note: In a real implementation, you would mint tokens on destination chain here
-        // For now, we'll just emit the event
+        let record = types::CycleRecord {
+            cycle: recurrence.current_cycle,
+            deadline: escrow.deadline,
+            total_amount: escrow.total_amount,
+            amount_collected,
+            status: closed_status.clone(),
+            closed_at: env.ledger().timestamp(),
+        };
+        storage::set_cycle_record(&env, &split_id_str, &record);
 
-        // Emit bridge completed event
-        events::emit_bridge_completed(&env, &bridge_id, &bridge.recipient);
+        let next_deadline = if escrow.has_next_cycle() {
+            escrow.open_next_cycle(&env);
+            Some(escrow.deadline)
+        } else {
+            escrow.status = closed_status;
+            None
+        };
+        storage::set_escrow(&env, &split_id_str, &escrow);
 
-        let current = storage::is_paused(&env);
-        storage::set_paused(&env, !current);
+        events::emit_cycle_rolled_over(&env, &split_id_str, recurrence.current_cycle, next_deadline);
+
+        Ok(recurrence.current_cycle)
     }
 
-    /// Internal helper function to release funds
-    fn release_funds_internal(env: &Env, split_id_str: String, mut escrow: SplitEscrow) -> Result<i128, Error> {
-        if escrow.status == EscrowStatus::Cancelled {
-            return Err(Error::SplitCancelled);
-        }
+    /// Get the record of a closed cycle of a recurring escrow
+    pub fn get_cycle(env: Env, split_id_str: String, cycle: u32) -> Option<types::CycleRecord> {
+        storage::get_cycle_record(&env, &split_id_str, cycle)
+    }
 
-        let total_amount = escrow.total_amount;
-        
-        let token_address = storage::get_token(env);
-        let token_client = TokenClient::new(env, &token_address);
-        token_client.transfer(&env.current_contract_address(), &escrow.creator, &total_amount);
+    /// Get every closed cycle of a recurring escrow, oldest first
+    pub fn get_cycle_history(env: Env, split_id_str: String) -> Vec<types::CycleRecord> {
+        let mut history = Vec::new(&env);
+        let escrow = match storage::get_escrow(&env, &split_id_str) {
+            Some(escrow) => escrow,
+            None => return history,
+        };
 
-        escrow.status = EscrowStatus::Released;
-        storage::set_escrow(env, &split_id_str, &escrow);
+        if let Some(recurrence) = escrow.recurrence_config() {
+            for cycle in 0..=recurrence.current_cycle {
+                if let Some(record) = storage::get_cycle_record(&env, &split_id_str, cycle) {
+                    history.push_back(record);
+                }
+            }
+        }
+        history
+    }
 
-        events::emit_funds_released(env, 0, escrow.creator.clone(), total_amount);
+    /// Pay the creator whatever has vested so far
+    ///
+    /// Anyone can call this since funds only ever go to the creator.
+    /// Returns the total amount paid out across all assets.
+    pub fn claim_vested(env: Env, split_id_str: String) -> Result<i128, Error> {
+        if storage::is_paused(&env, types::PauseGroup::Releases) {
+            panic!("Contract is paused");
+        }
 
-    /// Helper function to hash secret (simplified implementation)
-    fn hash_secret(env: &Env, _secret: &String) -> String {
-        // Simplified hash for demo — production would use sha256 via env.crypto()
-        String::from_str(env, "hash_stub")
-    }
+        if !storage::has_escrow(&env, &split_id_str) {
+            return Err(Error::SplitNotFound);
+        }
 
-    pub fn get_split(env: Env, split_id_str: String) -> SplitEscrow {
         let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
-        if escrow.status == EscrowStatus::Active && env.ledger().timestamp() > escrow.deadline {
-            escrow.status = EscrowStatus::Expired;
+
+        if escrow.vesting().is_none() {
+            panic!("Escrow has no vesting schedule");
         }
-        escrow
-    }
-}
 
-    /// Internal helper function to release funds
-    ///
-    /// Issue #201: groups collected payments by asset and transfers
-    /// the aggregated amount of each token to the split creator.
-    fn release_funds_internal(env: &Env, split_id: u64, mut split: types::Split) -> Result<i128, Error> {
-        if split.status == types::SplitStatus::Cancelled {
+        if escrow.status == EscrowStatus::Cancelled {
             return Err(Error::SplitCancelled);
         }
 
-        if split.status == types::SplitStatus::Released {
+        if escrow.status == EscrowStatus::Released {
             return Err(Error::SplitReleased);
         }
 
-        if !Self::is_fully_funded_internal(&split) {
+        // Past the cutoff an underfunded escrow is only refundable; streaming
+        // the same pool to the creator would race participants' refunds
+        if escrow.status == EscrowStatus::Expired || escrow.is_expired(env.ledger().timestamp()) {
             return Err(Error::SplitNotFunded);
         }
 
-        let total_amount = split.total_amount;
-        split.status = types::SplitStatus::Released;
-        split.amount_released = total_amount;
-        storage::set_split(env, split_id, &split);
+        Self::check_release_allowed_internal(&env, &split_id_str, &escrow)?;
 
-        let contract_address = env.current_contract_address();
+        let paid = Self::pay_vested_internal(&env, &mut escrow);
+        if paid <= 0 {
+            return Err(Error::NoFundsAvailable);
+        }
+
+        // Fully funded and fully vested: nothing left to stream
+        let schedule = escrow.vesting().unwrap();
+        if escrow.is_fully_funded() && env.ledger().timestamp() >= schedule.end {
+            escrow.status = EscrowStatus::Released;
+            Self::record_settlement_activity_internal(&env, &split_id_str, &escrow);
+        }
+
+        storage::set_escrow(&env, &split_id_str, &escrow);
+
+        Ok(paid)
+    }
 
-        // Aggregate the amount paid per asset token across all participants
-        // We use a Map keyed by Address (token contract) → total paid with that token.
-        let mut asset_totals: Map<Address, i128> = Map::new(env);
+    /// Configure payout routing for an escrow
+    ///
+    /// Only the creator can set payees, and only before any deposit
+    /// has been made so participants know where their money goes.
+    pub fn set_payees(env: Env, split_id_str: String, payees: Vec<types::Payee>) {
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        escrow.creator.require_auth();
 
-        for i in 0..split.participants.len() {
-            let p = split.participants.get(i).unwrap();
-            let current = asset_totals.get(p.asset.clone()).unwrap_or(0);
-            asset_totals.set(p.asset.clone(), current + p.amount_paid);
+        if escrow.status != EscrowStatus::Active {
+            panic!("Escrow is not active");
         }
 
-        // Transfer the aggregated amount of each asset to the creator
-        for (asset_addr, amount) in asset_totals.iter() {
-            if amount > 0 {
-                let token_client = TokenClient::new(env, &asset_addr);
-                token_client.transfer(&contract_address, &split.creator, &amount);
-            }
+        if escrow.amount_collected > 0 {
+            panic!("Payees cannot change after deposits");
         }
 
-        events::emit_escrow_completed(env, split_id, total_amount);
-        events::emit_funds_released(
-            env,
-            split_id,
-            &split.creator,
-            total_amount,
-            env.ledger().timestamp(),
-        );
+        if escrow.vesting().is_some() {
+            panic!("Vesting is not supported with payees");
+        }
 
-        Ok(total_amount)
+        escrow.payees = payees;
+        if let Err(reason) = escrow.validate_payees() {
+            panic!("{}", reason);
+        }
+
+        storage::set_escrow(&env, &split_id_str, &escrow);
     }
-}
\ No newline at end of file
+
+    /// Get the contract admin
+    pub fn get_admin(env: Env) -> Address {
+        storage::get_admin(&env)
+    }
+
+    /// Get the token contract address
+    pub fn get_token(env: Env) -> Address {
+        storage::get_token(&env)
+    }
+
+    // ============================================
+    // Insurance Query Functions
+    // ============================================
+
+    /// Get insurance policy by ID
+    pub fn get_insurance(env: Env, insurance_id: String) -> types::InsurancePolicy {
+        storage::get_insurance(&env, &insurance_id)
+    }
+
+    /// Get insurance claim by ID
+    pub fn get_claim(env: Env, claim_id: String) -> types::InsuranceClaim {
+        storage::get_claim(&env, &claim_id)
+    }
+
+    /// Get all claims for an insurance policy
+    pub fn get_insurance_claims(env: Env, insurance_id: String) -> Vec<String> {
+        storage::get_insurance_claims(&env, &insurance_id)
+    }
+
+    /// Check if a split has insurance
+    pub fn has_split_insurance(env: Env, split_id: String) -> bool {
+        storage::has_split_insurance(&env, &split_id)
+    }
+
+    /// Get insurance ID for a split
+    pub fn get_split_insurance(env: Env, split_id: String) -> Option<String> {
+        storage::get_split_to_insurance(&env, &split_id)
+    }
+
+    /// Get the balance of the premium pool
+    pub fn get_premium_pool(env: Env) -> i128 {
+        storage::get_premium_pool(&env)
+    }
+
+    // ============================================
+    // Insurance Lifecycle
+    // ============================================
+
+    /// Set the premium rate and claim window for new policies. Admin-only.
+    pub fn set_insurance_config(env: Env, config: types::InsuranceConfig) {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if let Err(reason) = config.validate() {
+            panic!("{}", reason);
+        }
+
+        storage::set_insurance_config(&env, &config);
+    }
+
+    /// Add funds to the premium pool
+    ///
+    /// Anyone can top up the pool that pays claims, in the default token.
+    pub fn fund_premium_pool(env: Env, funder: Address, amount: i128) {
+        funder.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let token_client = TokenClient::new(&env, &storage::get_token(&env));
+        token_client.transfer(&funder, &env.current_contract_address(), &amount);
+        storage::set_premium_pool(&env, storage::get_premium_pool(&env) + amount);
+    }
+
+    /// Insure an escrow against participants defaulting
+    ///
+    /// Only the creator can insure their escrow, once, for up to its total.
+    /// The premium is `premium_rate` basis points of `coverage`, paid in the
+    /// default token into the premium pool. Cover runs until
+    /// `claim_window` seconds after the payment deadline.
+    pub fn purchase_insurance(env: Env, split_id: String, coverage: i128) -> Result<String, Error> {
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
+            panic!("Contract is paused");
+        }
+
+        let escrow = storage::get_escrow(&env, &split_id).ok_or(Error::SplitNotFound)?;
+        escrow.creator.require_auth();
+
+        let config = storage::get_insurance_config(&env).expect("Insurance is not configured");
+
+        if escrow.status != EscrowStatus::Active {
+            panic!("Escrow is not active");
+        }
+        if storage::has_split_insurance(&env, &split_id) {
+            return Err(Error::InsuranceAlreadyExists);
+        }
+        if coverage <= 0 || coverage > escrow.total_amount {
+            return Err(Error::InvalidAmount);
+        }
+
+        let premium = config.premium_for(coverage);
+        let token_client = TokenClient::new(&env, &storage::get_token(&env));
+        token_client.transfer(&escrow.creator, &env.current_contract_address(), &premium);
+        storage::set_premium_pool(&env, storage::get_premium_pool(&env) + premium);
+
+        let insurance_id = storage::format_number_as_string(&env, storage::get_next_insurance_id(&env));
+        let policy = types::InsurancePolicy {
+            insurance_id: insurance_id.clone(),
+            split_id: split_id.clone(),
+            policy_holder: escrow.creator.clone(),
+            premium,
+            coverage_amount: coverage,
+            status: types::InsuranceStatus::Active,
+            created_at: env.ledger().timestamp(),
+            expires_at: escrow.payment_cutoff() + config.claim_window,
+            paid_out: 0,
+        };
+        storage::set_insurance(&env, &insurance_id, &policy);
+        storage::set_split_to_insurance(&env, &split_id, &insurance_id);
+
+        events::emit_insurance_purchased(&env, &insurance_id, &split_id, &escrow.creator, premium, coverage);
+
+        Ok(insurance_id)
+    }
+
+    /// File a claim against a policy for review by the admin or an oracle
+    pub fn file_claim(
+        env: Env,
+        insurance_id: String,
+        reason: String,
+        claim_amount: i128,
+    ) -> Result<String, Error> {
+        if !storage::has_insurance(&env, &insurance_id) {
+            return Err(Error::InsuranceNotFound);
+        }
+        let policy = storage::get_insurance(&env, &insurance_id);
+        policy.policy_holder.require_auth();
+
+        if policy.status != types::InsuranceStatus::Active {
+            return Err(Error::InvalidClaimStatus);
+        }
+        if env.ledger().timestamp() > policy.expires_at {
+            return Err(Error::InsuranceExpired);
+        }
+        if claim_amount <= 0 || claim_amount > policy.remaining_coverage() {
+            return Err(Error::InvalidAmount);
+        }
+
+        let claim_id = storage::format_number_as_string(&env, storage::get_next_claim_id(&env));
+        let claim = types::InsuranceClaim {
+            claim_id: claim_id.clone(),
+            insurance_id: insurance_id.clone(),
+            claimant: policy.policy_holder.clone(),
+            reason,
+            claim_amount,
+            status: types::ClaimStatus::Pending,
+            filed_at: env.ledger().timestamp(),
+            processed_at: None,
+            notes: None,
+        };
+        storage::set_claim(&env, &claim_id, &claim);
+        storage::add_insurance_claim(&env, &insurance_id, &claim_id);
+
+        events::emit_claim_filed(&env, &claim_id, &insurance_id, &policy.policy_holder, claim_amount);
+
+        Ok(claim_id)
+    }
+
+    /// Approve or reject a pending claim
+    ///
+    /// Callable by the admin or any configured oracle. Approved claims are
+    /// paid from the premium pool straight away, capped at the coverage
+    /// left on the policy. Returns the amount paid.
+    pub fn process_claim(
+        env: Env,
+        claim_id: String,
+        processor: Address,
+        approved: bool,
+        notes: Option<String>,
+    ) -> Result<i128, Error> {
+        processor.require_auth();
+
+        let is_oracle = storage::get_oracle_config(&env)
+            .is_some_and(|config| config.oracle_addresses.contains(&processor));
+        if processor != storage::get_admin(&env) && !is_oracle {
+            return Err(Error::OracleNotAuthorized);
+        }
+
+        if !storage::has_claim(&env, &claim_id) {
+            return Err(Error::ClaimNotFound);
+        }
+        let mut claim = storage::get_claim(&env, &claim_id);
+        if claim.status != types::ClaimStatus::Pending {
+            return Err(Error::InvalidClaimStatus);
+        }
+
+        claim.processed_at = Some(env.ledger().timestamp());
+        claim.notes = notes;
+
+        let payout = if approved {
+            let mut policy = storage::get_insurance(&env, &claim.insurance_id);
+            let amount = claim.claim_amount.min(policy.remaining_coverage());
+            Self::pay_insurance_internal(&env, &mut policy, &claim_id, amount)?;
+            claim.status = types::ClaimStatus::Paid;
+            amount
+        } else {
+            claim.status = types::ClaimStatus::Rejected;
+            0
+        };
+        storage::set_claim(&env, &claim_id, &claim);
+
+        events::emit_claim_processed(&env, &claim_id, &claim.insurance_id, approved, payout);
+
+        Ok(payout)
+    }
+
+    /// Pay out an insured escrow whose participants defaulted
+    ///
+    /// Once the payment deadline (plus any grace period) has passed on an
+    /// escrow that isn't fully funded, anyone can trigger this. The policy
+    /// holder receives the unpaid shares, capped at the remaining coverage
+    /// and at the deposits still held (what was paid in and not refunded),
+    /// recorded as an automatically approved claim. Those deposits are then
+    /// released to the payees, so they can no longer be refunded. No payout
+    /// is made if the creator is one of the defaulters. Returns the amount paid.
+    pub fn claim_default_payout(env: Env, split_id: String) -> Result<i128, Error> {
+        let escrow = storage::get_escrow(&env, &split_id).ok_or(Error::SplitNotFound)?;
+        let insurance_id = storage::get_split_to_insurance(&env, &split_id).ok_or(Error::InsuranceNotFound)?;
+        let mut policy = storage::get_insurance(&env, &insurance_id);
+
+        if policy.status != types::InsuranceStatus::Active {
+            return Err(Error::InvalidClaimStatus);
+        }
+        if escrow.status == EscrowStatus::Cancelled || escrow.status == EscrowStatus::Released {
+            return Err(Error::InvalidClaimStatus);
+        }
+
+        let now = env.ledger().timestamp();
+        if escrow.is_fully_funded() {
+            return Err(Error::SplitFullyFunded);
+        }
+        if now <= escrow.payment_cutoff() {
+            return Err(Error::InvalidClaimStatus);
+        }
+        if now > policy.expires_at {
+            return Err(Error::InsuranceExpired);
+        }
+
+        if escrow
+            .find_participant(&escrow.creator)
+            .is_some_and(|p| !p.has_fully_paid())
+        {
+            // The creator can't insure against their own default
+            return Err(Error::InvalidClaimStatus);
+        }
+
+        // Refunds already come off amount_collected
+        let held = escrow.amount_collected;
+        if held <= 0 {
+            return Err(Error::NoFundsAvailable);
+        }
+        let payout = escrow.remaining_amount().min(held).min(policy.remaining_coverage());
+
+        let claim_id = storage::format_number_as_string(&env, storage::get_next_claim_id(&env));
+        Self::pay_insurance_internal(&env, &mut policy, &claim_id, payout)?;
+
+        // A default can only be claimed once per policy
+        policy.status = types::InsuranceStatus::Claimed;
+        storage::set_insurance(&env, &insurance_id, &policy);
+
+        let claim = types::InsuranceClaim {
+            claim_id: claim_id.clone(),
+            insurance_id: insurance_id.clone(),
+            claimant: policy.policy_holder.clone(),
+            reason: String::from_str(&env, "Participant default"),
+            claim_amount: payout,
+            status: types::ClaimStatus::Paid,
+            filed_at: now,
+            processed_at: Some(now),
+            notes: None,
+        };
+        storage::set_claim(&env, &claim_id, &claim);
+        storage::add_insurance_claim(&env, &insurance_id, &claim_id);
+
+        events::emit_claim_filed(&env, &claim_id, &insurance_id, &policy.policy_holder, payout);
+        events::emit_claim_processed(&env, &claim_id, &insurance_id, true, payout);
+
+        // Paid-in deposits go to the payees rather than back to participants
+        Self::release_funds_internal(&env, split_id, escrow)?;
+
+        Ok(payout)
+    }
+
+    /// Get a user's rewards record (activity totals and claims)
+    pub fn get_user_rewards(env: Env, user: Address) -> Option<types::UserRewards> {
+        storage::get_user_rewards(&env, &user)
+    }
+
+    /// Calculate rewards for a user
+    ///
+    /// This function calculates the total rewards earned by a user based on their activity.
+    pub fn calculate_rewards(
+        env: Env,
+        user: Address,
+    ) -> i128 {
+        // Get user rewards data
+        let rewards = storage::get_user_rewards(&env, &user)
+            .unwrap_or(types::UserRewards {
+                user: user.clone(),
+                total_splits_created: 0,
+                total_splits_participated: 0,
+                total_amount_transacted: 0,
+                rewards_earned: 0,
+                rewards_claimed: 0,
+                last_activity: env.ledger().timestamp(),
+                status: types::RewardsStatus::Active,
+            });
+
+        // Calculate rewards based on activity
+        let total_rewards = rewards.total_points();
+
+        // Update rewards earned
+        let rewards_claimed = rewards.rewards_claimed;
+        let mut updated_rewards = rewards;
+        updated_rewards.rewards_earned = total_rewards;
+        storage::set_user_rewards(&env, &user, &updated_rewards);
+
+        // Calculate available rewards (earned - claimed)
+        let available_rewards = total_rewards - rewards_claimed;
+
+        // Emit rewards calculated event
+        events::emit_rewards_calculated(&env, &user, total_rewards, available_rewards);
+
+        total_rewards
+    }
+
+    /// Set the reward token, conversion rate and epoch cap. Admin-only.
+    pub fn set_rewards_config(env: Env, config: types::RewardsConfig) {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if let Err(reason) = config.validate() {
+            panic!("{}", reason);
+        }
+        if let Some(current) = storage::get_rewards_config(&env) {
+            if current.reward_token != config.reward_token && storage::get_rewards_treasury(&env) > 0 {
+                panic!("Cannot change reward token while the treasury holds funds");
+            }
+        }
+
+        storage::set_rewards_config(&env, &config);
+    }
+
+    /// Deposit reward tokens into the rewards treasury. Treasurer-only.
+    pub fn fund_rewards_treasury(env: Env, caller: Address, amount: i128) {
+        Self::require_role_internal(&env, &caller, types::Role::Treasurer);
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let config = storage::get_rewards_config(&env).expect("Rewards are not configured");
+        let token_client = TokenClient::new(&env, &config.reward_token);
+        token_client.transfer(&caller, &env.current_contract_address(), &amount);
+        storage::set_rewards_treasury(&env, storage::get_rewards_treasury(&env) + amount);
+    }
+
+    /// Get the rewards treasury balance
+    pub fn get_rewards_treasury(env: Env) -> i128 {
+        storage::get_rewards_treasury(&env)
+    }
+
+    /// Suspend or reinstate a user's rewards. Admin-only.
+    pub fn set_rewards_status(env: Env, user: Address, status: types::RewardsStatus) -> Result<(), Error> {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let mut rewards = storage::get_user_rewards(&env, &user).ok_or(Error::UserNotFound)?;
+        rewards.status = status;
+        storage::set_user_rewards(&env, &user, &rewards);
+
+        Ok(())
+    }
+
+    /// Claim unclaimed reward points as tokens from the treasury
+    ///
+    /// Points convert at `tokens_per_point`. A claim is cut down to what
+    /// the treasury and this epoch's emission cap can cover; the rest stays
+    /// claimable later. Returns the tokens paid.
+    pub fn claim_rewards(env: Env, user: Address) -> Result<i128, Error> {
+        user.require_auth();
+
+        if storage::is_paused(&env, types::PauseGroup::Releases) {
+            panic!("Contract is paused");
+        }
+
+        let config = storage::get_rewards_config(&env).expect("Rewards are not configured");
+        let mut rewards = storage::get_user_rewards(&env, &user).ok_or(Error::UserNotFound)?;
+
+        if rewards.status == types::RewardsStatus::Suspended {
+            return Err(Error::RewardsSuspended);
+        }
+
+        let total_points = rewards.total_points();
+        let unclaimed = total_points - rewards.rewards_claimed;
+        if unclaimed <= 0 {
+            return Err(Error::RewardsAlreadyClaimed);
+        }
+
+        let epoch = config.epoch_at(env.ledger().timestamp());
+        let emitted = storage::get_epoch_emitted(&env, epoch);
+        let treasury = storage::get_rewards_treasury(&env);
+        let budget = treasury.min(config.epoch_cap - emitted);
+
+        let points = unclaimed.min(budget / config.tokens_per_point);
+        if points <= 0 {
+            return Err(Error::InsufficientRewards);
+        }
+        let payout = points * config.tokens_per_point;
+
+        let token_client = TokenClient::new(&env, &config.reward_token);
+        token_client.transfer(&env.current_contract_address(), &user, &payout);
+
+        storage::set_rewards_treasury(&env, treasury - payout);
+        storage::set_epoch_emitted(&env, epoch, emitted + payout);
+
+        rewards.rewards_earned = total_points;
+        rewards.rewards_claimed += points;
+        storage::set_user_rewards(&env, &user, &rewards);
+
+        events::emit_rewards_claimed(&env, &user, payout);
+
+        Ok(payout)
+    }
+
+    /// Claim a refund for a cancelled or expired split
+    pub fn claim_refund(env: Env, split_id_str: String, participant: Address) -> Result<i128, Error> {
+        if storage::is_paused(&env, types::PauseGroup::Refunds) {
+            panic!("Contract is paused");
+        }
+
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+
+        // Check if escrow is in a refundable state
+        if escrow.is_expired(env.ledger().timestamp()) {
+            escrow.status = EscrowStatus::Expired;
+            storage::set_escrow(&env, &split_id_str, &escrow);
+            events::emit_escrow_expired(&env, &split_id_str, escrow.remaining_amount());
+        }
+
+        if escrow.status != EscrowStatus::Cancelled && escrow.status != EscrowStatus::Expired {
+            return Err(Error::EscrowNotRefundable);
+        }
+
+        let mut found = false;
+        let mut refund_amount: i128 = 0;
+        let mut updated_participants = Vec::new(&env);
+
+        for i in 0..escrow.participants.len() {
+            let mut p = escrow.participants.get(i).unwrap();
+            if p.address == participant {
+                found = true;
+                participant.require_auth();
+
+                if p.amount_paid <= 0 {
+                    return Err(Error::NoFundsAvailable);
+                }
+
+                // Money goes back to whoever actually paid, in the same token
+                refund_amount = escrow.refundable_amount(&p.asset, p.amount_paid);
+                Self::refund_amount_internal(&env, &mut escrow, &p, p.amount_paid, refund_amount);
+                p.amount_paid = 0;
+                p.paid_at = None;
+            }
+            updated_participants.push_back(p);
+        }
+
+        if !found {
+            return Err(Error::ParticipantNotFound);
+        }
+
+        escrow.participants = updated_participants;
+        storage::set_escrow(&env, &split_id_str, &escrow);
+
+        Ok(refund_amount)
+    }
+
+    // ============================================
+    // Disputes
+    // ============================================
+
+    /// Register the dispute-resolution contract consulted before releases. Admin-only.
+    pub fn set_dispute_contract(env: Env, contract: Address) {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_dispute_contract(&env, &contract);
+    }
+
+    /// Open a dispute against an escrow in the dispute contract
+    ///
+    /// Only the creator or a participant can raise one, and only while the
+    /// escrow still holds funds to release. Releases only ever consult
+    /// disputes opened here, so disputes raised directly with the dispute
+    /// contract can't hold an escrow. Each account can raise at most
+    /// `MAX_DISPUTES` disputes per escrow. Returns the new dispute ID.
+    pub fn raise_dispute(env: Env, split_id_str: String, raiser: Address, reason: String) -> Result<String, Error> {
+        raiser.require_auth();
+
+        let escrow = storage::get_escrow(&env, &split_id_str).ok_or(Error::SplitNotFound)?;
+        if escrow.status == EscrowStatus::Released || escrow.status == EscrowStatus::Cancelled {
+            return Err(Error::EscrowNotRefundable);
+        }
+
+        if raiser != escrow.creator && escrow.find_participant(&raiser).is_none() {
+            return Err(Error::ParticipantNotFound);
+        }
+
+        // One dispute at a time, so checks before a release stay bounded
+        if Self::check_dispute_internal(&env, &split_id_str) == Err(Error::DisputeOpen) {
+            return Err(Error::DisputeOpen);
+        }
+
+        if storage::get_disputes_raised(&env, &split_id_str, &raiser) >= types::MAX_DISPUTES {
+            return Err(Error::FlagLimitReached);
+        }
+
+        let contract = storage::get_dispute_contract(&env).expect("Dispute contract is not configured");
+        let dispute_id = dispute::DisputeClient::new(&env, &contract).raise_dispute(&split_id_str, &raiser, &reason);
+        storage::add_escrow_dispute(&env, &split_id_str, &dispute_id);
+        storage::increment_disputes_raised(&env, &split_id_str, &raiser);
+
+        Ok(dispute_id)
+    }
+
+    /// Refund an escrow after a dispute against it was upheld
+    ///
+    /// Anyone can call this once the dispute contract reports an
+    /// UpheldForRaiser result. The escrow is cancelled and every
+    /// participant refunded. Returns the total refunded.
+    pub fn settle_dispute(env: Env, split_id_str: String) -> Result<i128, Error> {
+        if storage::is_paused(&env, types::PauseGroup::Refunds) {
+            panic!("Contract is paused");
+        }
+
+        let mut escrow = storage::get_escrow(&env, &split_id_str).ok_or(Error::SplitNotFound)?;
+        if escrow.status == EscrowStatus::Released || escrow.status == EscrowStatus::Cancelled {
+            return Err(Error::EscrowNotRefundable);
+        }
+
+        if Self::check_dispute_internal(&env, &split_id_str) != Err(Error::DisputeUpheld) {
+            return Err(Error::EscrowNotRefundable);
+        }
+
+        escrow.status = EscrowStatus::Cancelled;
+        let refunded = Self::refund_all_internal(&env, &mut escrow);
+        storage::set_escrow(&env, &split_id_str, &escrow);
+
+        events::emit_escrow_cancelled(&env, &split_id_str, env.current_contract_address());
+
+        Ok(refunded)
+    }
+
+    // ============================================
+    // Multisig Approval
+    // ============================================
+
+    /// Register the multi-sig-splits contract that approves gated releases. Admin-only.
+    pub fn set_multisig_contract(env: Env, contract: Address) {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_multisig_contract(&env, &contract);
+    }
+
+    /// Require multisig approval before an escrow's funds are released
+    ///
+    /// Only the creator can set this, and only before any deposit. The
+    /// multisig split must use the same split ID as the escrow; only
+    /// signatures from the escrow's participants count toward it, and at
+    /// least a majority of them must sign. Until they do, the creator can
+    /// cancel the escrow, and once the payment cutoff passes anyone can
+    /// refund it through `settle_multisig`.
+    pub fn set_multisig_gate(env: Env, split_id_str: String, gated: bool) {
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        escrow.creator.require_auth();
+
+        if escrow.status != EscrowStatus::Active {
+            panic!("Escrow is not active");
+        }
+
+        if escrow.amount_collected > 0 {
+            panic!("Multisig gate cannot change after deposits");
+        }
+
+        if escrow.recurrence_config().is_some() {
+            panic!("Multisig gate is not supported with recurrence");
+        }
+
+        if gated && storage::get_multisig_contract(&env).is_none() {
+            panic!("Multisig contract is not configured");
+        }
+
+        escrow.multisig_gated = gated;
+        storage::set_escrow(&env, &split_id_str, &escrow);
+    }
+
+    /// Refund a gated escrow whose multisig approval won't come
+    ///
+    /// Anyone can call this once the multisig split was cancelled, or once
+    /// the escrow's payment cutoff has passed without approval (including
+    /// when no split was ever created). The escrow is cancelled and every
+    /// participant refunded. Returns the total refunded.
+    pub fn settle_multisig(env: Env, split_id_str: String) -> Result<i128, Error> {
+        if storage::is_paused(&env, types::PauseGroup::Refunds) {
+            panic!("Contract is paused");
+        }
+
+        let mut escrow = storage::get_escrow(&env, &split_id_str).ok_or(Error::SplitNotFound)?;
+        if !escrow.multisig_gated
+            || escrow.status == EscrowStatus::Released
+            || escrow.status == EscrowStatus::Cancelled
+        {
+            return Err(Error::EscrowNotRefundable);
+        }
+
+        let past_cutoff = env.ledger().timestamp() > escrow.payment_cutoff();
+        match Self::check_multisig_internal(&env, &split_id_str, &escrow) {
+            Err(Error::MultisigCancelled) => {}
+            Err(_) if past_cutoff => {}
+            _ => return Err(Error::EscrowNotRefundable),
+        }
+
+        escrow.status = EscrowStatus::Cancelled;
+        let refunded = Self::refund_all_internal(&env, &mut escrow);
+        storage::set_escrow(&env, &split_id_str, &escrow);
+
+        events::emit_escrow_cancelled(&env, &split_id_str, env.current_contract_address());
+
+        Ok(refunded)
+    }
+
+    /// Close out an escrow whose deadline has passed unfunded
+    ///
+    /// Anyone can call this. The escrow is stored as Expired and every
+    /// participant's payment is refunded in one go. The first call on an
+    /// escrow that refunds something pays `keeper` the configured bounty
+    /// from collected fees, capped at `MAX_KEEPER_BOUNTY_BPS` of the amount
+    /// refunded, if there are enough in the default token. Returns the
+    /// total refunded.
+    pub fn finalize_expired(env: Env, split_id_str: String, keeper: Address) -> Result<i128, Error> {
+        if storage::is_paused(&env, types::PauseGroup::Refunds) {
+            panic!("Contract is paused");
+        }
+
+        let mut escrow = storage::get_escrow(&env, &split_id_str).ok_or(Error::SplitNotFound)?;
+        if escrow.recurrence_config().is_some() {
+            panic!("Recurring escrows close through roll_over");
+        }
+
+        let newly_expired = escrow.is_expired(env.ledger().timestamp());
+        if !newly_expired && escrow.status != EscrowStatus::Expired {
+            return Err(Error::EscrowNotRefundable);
+        }
+
+        if newly_expired {
+            escrow.status = EscrowStatus::Expired;
+            events::emit_escrow_expired(&env, &split_id_str, escrow.remaining_amount());
+        } else if escrow.participants.iter().all(|p| p.amount_paid <= 0) {
+            // Already finalised and nothing left to hand back
+            return Err(Error::NoFundsAvailable);
+        }
+
+        let refunded = Self::refund_all_internal(&env, &mut escrow);
+        storage::set_escrow(&env, &split_id_str, &escrow);
+
+        if newly_expired {
+            Self::pay_keeper_bounty_internal(&env, &keeper, refunded);
+        }
+
+        Ok(refunded)
+    }
+
+    /// Flag an escrow's receipt for verification by the oracle set
+    ///
+    /// Only the creator or a participant can flag an escrow. While the
+    /// request is pending the escrow's funds cannot be released; once
+    /// `required_verifications` oracles agree it becomes Verified or
+    /// Rejected, and if they don't within `verification_timeout` it expires.
+    /// A rejected escrow can only be refunded, through `settle_verification`.
+    /// Each account can flag an escrow at most `MAX_VERIFICATION_FLAGS` times.
+    pub fn submit_verification(
+        env: Env,
+        split_id: String,
+        requester: Address,
+        receipt_hash: String,
+    ) -> Result<String, Error> {
+        requester.require_auth();
+
+        let escrow = storage::get_escrow(&env, &split_id).ok_or(Error::SplitNotFound)?;
+        if requester != escrow.creator && escrow.find_participant(&requester).is_none() {
+            return Err(Error::ParticipantNotFound);
+        }
+
+        if storage::get_verification_flags(&env, &split_id, &requester) >= types::MAX_VERIFICATION_FLAGS {
+            return Err(Error::FlagLimitReached);
+        }
+
+        let config = storage::get_oracle_config(&env).ok_or(Error::InsufficientOracles)?;
+        if config.required_verifications == 0 || config.oracle_addresses.len() < config.required_verifications {
+            return Err(Error::InsufficientOracles);
+        }
+
+        // One live request per escrow, and a verified receipt stays verified
+        if let Some(existing_id) = storage::get_escrow_verification(&env, &split_id) {
+            let existing = storage::get_verification_request(&env, &existing_id).unwrap();
+            let now = env.ledger().timestamp();
+            let live = existing.status == types::VerificationStatus::Pending
+                && !existing.is_timed_out(config.verification_timeout, now);
+            if live || existing.status == types::VerificationStatus::Verified {
+                return Err(Error::VerificationAlreadyExists);
+            }
+        }
+
+        // Generate verification ID
+        let verification_id = storage::get_next_verification_id(&env);
+
+        // Create verification request
+        let request = types::VerificationRequest {
+            verification_id: verification_id.clone(),
+            split_id: split_id.clone(),
+            requester: requester.clone(),
+            receipt_hash: receipt_hash.clone(),
+            evidence_url: None,
+            submitted_at: env.ledger().timestamp(),
+            status: types::VerificationStatus::Pending,
+            verified_by: None,
+            verified_at: None,
+            rejection_reason: None,
+            approvals: Vec::new(&env),
+            rejections: Vec::new(&env),
+        };
+
+        // Store verification request and flag the escrow
+        storage::set_verification_request(&env, &verification_id, &request);
+        storage::set_escrow_verification(&env, &split_id, &verification_id);
+        storage::increment_verification_flags(&env, &split_id, &requester);
+
+        // Emit verification submitted event
+        events::emit_verification_submitted(&env, &verification_id, &split_id, &requester);
+
+        Ok(verification_id)
+    }
+
+    /// Attest to a split's receipt
+    ///
+    /// Each configured oracle gets one attestation. The request is decided
+    /// as soon as `required_verifications` oracles agree either way.
+    pub fn verify_split(
+        env: Env,
+        verification_id: String,
+        oracle: Address,
+        verified: bool,
+    ) -> Result<types::VerificationStatus, Error> {
+        oracle.require_auth();
+
+        // Get verification request
+        let mut request = storage::get_verification_request(&env, &verification_id)
+            .ok_or(Error::VerificationNotFound)?;
+
+        // Check if caller is authorized oracle
+        let oracle_config = storage::get_oracle_config(&env)
+            .ok_or(Error::OracleNotAuthorized)?;
+
+        if !oracle_config.oracle_addresses.contains(&oracle) {
+            return Err(Error::OracleNotAuthorized);
+        }
+
+        let now = env.ledger().timestamp();
+        if request.status != types::VerificationStatus::Pending
+            || request.is_timed_out(oracle_config.verification_timeout, now)
+        {
+            return Err(Error::InvalidVerificationStatus);
+        }
+        if request.has_attested(&oracle) {
+            return Err(Error::AlreadyAttested);
+        }
+
+        if verified {
+            request.approvals.push_back(oracle.clone());
+        } else {
+            request.rejections.push_back(oracle.clone());
+        }
+        events::emit_verification_attested(&env, &verification_id, &oracle, verified);
+
+        // Decide once the quorum agrees
+        let required = oracle_config.required_verifications;
+        if request.approvals.len() >= required {
+            request.status = types::VerificationStatus::Verified;
+        } else if request.rejections.len() >= required {
+            request.status = types::VerificationStatus::Rejected;
+            request.rejection_reason = Some(String::from_str(&env, "Evidence insufficient"));
+        }
+
+        if request.status != types::VerificationStatus::Pending {
+            request.verified_by = Some(oracle.clone());
+            request.verified_at = Some(now);
+            events::emit_verification_completed(
+                &env,
+                &verification_id,
+                request.status == types::VerificationStatus::Verified,
+                &oracle,
+            );
+        }
+
+        storage::set_verification_request(&env, &verification_id, &request);
+
+        Ok(request.status)
+    }
+
+    /// Mark a pending verification that ran past its timeout as Expired
+    ///
+    /// Anyone can call this; an expired request no longer blocks release.
+    pub fn expire_verification(env: Env, verification_id: String) -> Result<(), Error> {
+        let mut request = storage::get_verification_request(&env, &verification_id)
+            .ok_or(Error::VerificationNotFound)?;
+        let config = storage::get_oracle_config(&env).ok_or(Error::InsufficientOracles)?;
+
+        if !request.is_timed_out(config.verification_timeout, env.ledger().timestamp()) {
+            return Err(Error::InvalidVerificationStatus);
+        }
+
+        request.status = types::VerificationStatus::Expired;
+        storage::set_verification_request(&env, &verification_id, &request);
+
+        events::emit_verification_expired(&env, &verification_id);
+
+        Ok(())
+    }
+
+    /// Refund an escrow whose receipt the oracles rejected
+    ///
+    /// Anyone can call this once the escrow's verification is Rejected.
+    /// The escrow is cancelled and every participant refunded. Returns
+    /// the total refunded.
+    pub fn settle_verification(env: Env, split_id_str: String) -> Result<i128, Error> {
+        if storage::is_paused(&env, types::PauseGroup::Refunds) {
+            panic!("Contract is paused");
+        }
+
+        let mut escrow = storage::get_escrow(&env, &split_id_str).ok_or(Error::SplitNotFound)?;
+        if escrow.status == EscrowStatus::Released || escrow.status == EscrowStatus::Cancelled {
+            return Err(Error::EscrowNotRefundable);
+        }
+
+        if Self::check_verification_internal(&env, &split_id_str) != Err(Error::VerificationRejected) {
+            return Err(Error::EscrowNotRefundable);
+        }
+
+        escrow.status = EscrowStatus::Cancelled;
+        let refunded = Self::refund_all_internal(&env, &mut escrow);
+        storage::set_escrow(&env, &split_id_str, &escrow);
+
+        events::emit_escrow_cancelled(&env, &split_id_str, env.current_contract_address());
+
+        Ok(refunded)
+    }
+
+    /// Get a verification request, showing Expired once it has timed out
+    pub fn get_verification(env: Env, verification_id: String) -> Result<types::VerificationRequest, Error> {
+        let mut request = storage::get_verification_request(&env, &verification_id)
+            .ok_or(Error::VerificationNotFound)?;
+        if let Some(config) = storage::get_oracle_config(&env) {
+            if request.is_timed_out(config.verification_timeout, env.ledger().timestamp()) {
+                request.status = types::VerificationStatus::Expired;
+            }
+        }
+        Ok(request)
+    }
+
+    /// Cancel a split and mark for refunds
+    pub fn cancel_split(env: Env, split_id_str: String) {
+        if storage::is_paused(&env, types::PauseGroup::Refunds) {
+            panic!("Contract is paused");
+        }
+
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        escrow.creator.require_auth();
+
+        // A fully funded streaming escrow can still be cancelled; whatever
+        // hasn't vested yet is refunded to participants. So can a gated one
+        // whose multisig approval hasn't come through.
+        let awaiting_multisig = escrow.multisig_gated
+            && Self::check_multisig_internal(&env, &split_id_str, &escrow).is_err();
+        if escrow.status == EscrowStatus::Completed && escrow.vesting().is_none() && !awaiting_multisig {
+            panic!("Cannot cancel a completed escrow");
+        }
+
+        if escrow.vesting().is_some() && Self::check_release_allowed_internal(&env, &split_id_str, &escrow).is_ok() {
+            // Settle what the creator has earned up to now before freezing
+            Self::pay_vested_internal(&env, &mut escrow);
+        }
+
+        escrow.status = EscrowStatus::Cancelled;
+        storage::set_escrow(&env, &split_id_str, &escrow);
+
+        events::emit_escrow_cancelled(&env, &split_id_str, escrow.creator.clone());
+    }
+
+    pub fn release_partial(env: Env, split_id_str: String) -> Result<i128, Error> {
+        if storage::is_paused(&env, types::PauseGroup::Releases) {
+            panic!("Contract is paused");
+        }
+
+        if !storage::has_escrow(&env, &split_id_str) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+
+        if escrow.status == EscrowStatus::Cancelled {
+            return Err(Error::SplitCancelled);
+        }
+
+        if !escrow.payees.is_empty() {
+            panic!("Partial release is not supported with payees");
+        }
+
+        if escrow.vesting().is_some() {
+            panic!("Vesting escrows release through claim_vested");
+        }
+
+        if escrow.late_policy().is_some() {
+            panic!("Partial release is not supported with late penalties");
+        }
+
+        Self::check_release_allowed_internal(&env, &split_id_str, &escrow)?;
+
+        // Pay out whatever is held in each asset that hasn't been released yet
+        let held = escrow.unreleased_by_asset(&env);
+        if held.is_empty() {
+            return Err(Error::NoFundsAvailable);
+        }
+
+        let mut available: i128 = 0;
+        for (asset_address, amount) in held.iter() {
+            Self::pay_out_internal(&env, &split_id_str, &asset_address, &escrow.creator, amount);
+            escrow.record_asset_release(&asset_address, amount);
+            available += amount;
+        }
+
+        storage::set_escrow(&env, &split_id_str, &escrow);
+
+        Ok(available)
+    }
+
+    pub fn is_fully_funded(env: Env, split_id_str: String) -> Result<bool, Error> {
+        if !storage::has_escrow(&env, &split_id_str) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        Ok(escrow.is_fully_funded())
+    }
+
+    /// Extend escrow deadline
+    pub fn extend_deadline(env: Env, split_id_str: String, new_deadline: u64) {
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
+            panic!("Contract is paused");
+        }
+
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        escrow.creator.require_auth();
+
+        if new_deadline <= escrow.deadline {
+            panic!("New deadline must be later than current");
+        }
+
+        if escrow.status != EscrowStatus::Active {
+            panic!("Escrow is not active");
+        }
+
+        escrow.deadline = new_deadline;
+        storage::set_escrow(&env, &split_id_str, &escrow);
+    }
+
+    // ============================================
+    // Oracle Network
+    // ============================================
+
+    /// Configure the oracle network. Oracle-manager-only.
+    pub fn set_oracle_config(env: Env, caller: Address, config: types::OracleConfig) {
+        Self::require_role_internal(&env, &caller, types::Role::OracleManager);
+
+        if config.min_oracles == 0 {
+            panic!("At least one oracle is required for consensus");
+        }
+        if config.min_stake <= 0 {
+            panic!("Minimum stake must be positive");
+        }
+
+        storage::set_oracle_config(&env, &config);
+    }
+
+    /// Register as a price oracle by locking a stake
+    ///
+    /// The stake is paid in the contract's default token and must be at
+    /// least `OracleConfig.min_stake`.
+    pub fn register_oracle(env: Env, oracle: Address, stake: i128) -> Result<(), Error> {
+        oracle.require_auth();
+
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
+            panic!("Contract is paused");
+        }
+
+        let config = storage::get_oracle_config(&env).ok_or(Error::OracleNotAuthorized)?;
+        if stake < config.min_stake {
+            return Err(Error::InsufficientStake);
+        }
+        if storage::has_oracle_node(&env, &oracle) {
+            return Err(Error::OracleAlreadyRegistered);
+        }
+
+        let token_client = TokenClient::new(&env, &storage::get_token(&env));
+        token_client.transfer(&oracle, &env.current_contract_address(), &stake);
+
+        let node = types::OracleNode {
+            oracle_address: oracle.clone(),
+            stake,
+            reputation: types::INITIAL_ORACLE_REPUTATION,
+            submissions_count: 0,
+            last_submission: 0,
+            active: true,
+        };
+        storage::set_oracle_node(&env, &oracle, &node);
+        storage::add_registered_oracle(&env, &oracle);
+
+        events::emit_oracle_registered(&env, &oracle, stake);
+
+        Ok(())
+    }
+
+    /// Get a registered oracle node
+    pub fn get_oracle(env: Env, oracle: Address) -> Result<types::OracleNode, Error> {
+        storage::get_oracle_node(&env, &oracle).ok_or(Error::OracleNotRegistered)
+    }
+
+    /// Submit a price for an asset pair
+    ///
+    /// Only active registered oracles may report. Each oracle has one live
+    /// submission per pair; a new one replaces the old.
+    pub fn submit_price(env: Env, oracle: Address, asset_pair: String, price: i128) -> Result<(), Error> {
+        oracle.require_auth();
+
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
+            panic!("Contract is paused");
+        }
+
+        let mut oracle_node = storage::get_oracle_node(&env, &oracle).ok_or(Error::OracleNotRegistered)?;
+        if !oracle_node.active {
+            return Err(Error::OracleNotRegistered);
+        }
+        if price <= 0 {
+            return Err(Error::PriceSubmissionInvalid);
+        }
+
+        // Create price submission
+        let submission = types::PriceSubmission {
+            oracle_address: oracle.clone(),
+            asset_pair: asset_pair.clone(),
+            price,
+            timestamp: env.ledger().timestamp(),
+            // The submitter is authenticated by require_auth above
+            signature: String::from_str(&env, ""),
+            evaluated: false,
+        };
+
+        // Store submission
+        storage::set_price_submission(&env, &asset_pair, &oracle, &submission);
+
+        // Update oracle node
+        oracle_node.submissions_count += 1;
+        oracle_node.last_submission = env.ledger().timestamp();
+        storage::set_oracle_node(&env, &oracle, &oracle_node);
+
+        // Emit price submitted event
+        events::emit_price_submitted(&env, &oracle, &asset_pair, price);
+
+        // Publish a new consensus once enough oracles have reported
+        if let Ok(consensus) = Self::calculate_consensus_price_internal(&env, &asset_pair) {
+            storage::set_consensus_price(&env, &asset_pair, &consensus);
+            events::emit_consensus_reached(
+                &env,
+                &asset_pair,
+                consensus.price,
+                consensus.confidence,
+                consensus.participating_oracles,
+            );
+            Self::score_submissions_internal(&env, &asset_pair, consensus.price);
+        }
+
+        Ok(())
+    }
+
+    /// Configure penalties for outlier price submissions. Oracle-manager-only.
+    pub fn set_slashing_config(env: Env, caller: Address, config: types::SlashingConfig) {
+        Self::require_role_internal(&env, &caller, types::Role::OracleManager);
+
+        if let Err(reason) = config.validate() {
+            panic!("{}", reason);
+        }
+
+        storage::set_slashing_config(&env, &config);
+    }
+
+    /// Score fresh submissions for a pair against its new consensus
+    ///
+    /// Each submission is scored once. Outliers lose reputation and part
+    /// of their stake (sent to the treasury); oracles that fall below the
+    /// reputation floor are deactivated and stop counting toward consensus.
+    fn score_submissions_internal(env: &Env, asset_pair: &String, consensus: i128) {
+        let config = match storage::get_slashing_config(env) {
+            Some(config) => config,
+            None => return,
+        };
+        let token_client = TokenClient::new(env, &storage::get_token(env));
+
+        for mut submission in Self::fresh_submissions_internal(env, asset_pair).iter() {
+            if submission.evaluated {
+                continue;
+            }
+            submission.evaluated = true;
+            storage::set_price_submission(env, asset_pair, &submission.oracle_address, &submission);
+
+            if !config.is_outlier(submission.price, consensus) {
+                continue;
+            }
+
+            let oracle = submission.oracle_address;
+            let mut node = storage::get_oracle_node(env, &oracle).unwrap();
+            let slashed = config.slash_amount(node.stake);
+            if slashed > 0 {
+                token_client.transfer(&env.current_contract_address(), &config.treasury, &slashed);
+                node.stake -= slashed;
+            }
+            node.reputation = node.reputation.saturating_sub(config.reputation_penalty);
+            events::emit_oracle_slashed(env, &oracle, asset_pair, slashed, node.reputation);
+
+            if node.reputation < config.reputation_floor {
+                node.active = false;
+                events::emit_oracle_deactivated(env, &oracle);
+            }
+            storage::set_oracle_node(env, &oracle, &node);
+        }
+    }
+
+    /// Calculate consensus price from oracle submissions
+    ///
+    /// Takes the median of fresh submissions from active oracles. Fails
+    /// with `InsufficientOracles` if fewer than `min_oracles` have reported
+    /// within the staleness window.
+    fn calculate_consensus_price_internal(env: &Env, asset_pair: &String) -> Result<types::ConsensusPrice, Error> {
+        let config = storage::get_oracle_config(env).ok_or(Error::InsufficientOracles)?;
+
+        let mut prices = Vec::new(env);
+        for submission in Self::fresh_submissions_internal(env, asset_pair).iter() {
+            prices.push_back(submission.price);
+        }
+
+        if prices.len() < config.min_oracles {
+            return Err(Error::InsufficientOracles);
+        }
+
+        Ok(types::compute_consensus(env, asset_pair, &prices, env.ledger().timestamp()))
+    }
+
+    /// Submissions for a pair from active oracles within the staleness window
+    fn fresh_submissions_internal(env: &Env, asset_pair: &String) -> Vec<types::PriceSubmission> {
+        let mut fresh = Vec::new(env);
+        let config = match storage::get_oracle_config(env) {
+            Some(config) => config,
+            None => return fresh,
+        };
+        let now = env.ledger().timestamp();
+
+        for oracle in storage::get_registered_oracles(env).iter() {
+            let active = storage::get_oracle_node(env, &oracle).is_some_and(|node| node.active);
+            if !active {
+                continue;
+            }
+            if let Some(submission) = storage::get_price_submission(env, asset_pair, &oracle) {
+                if submission.timestamp + config.price_staleness >= now {
+                    fresh.push_back(submission);
+                }
+            }
+        }
+
+        fresh
+    }
+
+    /// Get consensus price for asset pair
+    ///
+    /// This function returns the consensus price from the oracle network,
+    /// computed from the submissions that are fresh right now.
+    pub fn get_consensus_price(
+        env: Env,
+        asset_pair: String,
+    ) -> Result<i128, Error> {
+        let consensus = Self::calculate_consensus_price_internal(&env, &asset_pair)?;
+        Ok(consensus.price)
+    }
+
+    /// Get the full consensus (price, confidence, participating oracles)
+    pub fn get_consensus(env: Env, asset_pair: String) -> Result<types::ConsensusPrice, Error> {
+        Self::calculate_consensus_price_internal(&env, &asset_pair)
+    }
+
+    // ============================================
+    // Protocol Fees
+    // ============================================
+
+    /// Set the protocol fee rate, caps and treasury. Admin-only.
+    pub fn set_fee_config(env: Env, config: types::FeeConfig) {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if let Err(reason) = config.validate() {
+            panic!("{}", reason);
+        }
+
+        storage::set_fee_config(&env, &config);
+    }
+
+    /// Override the fee rate for one asset, or clear the override. Admin-only.
+    pub fn set_asset_fee_rate(env: Env, asset: Address, fee_bps: Option<u32>) {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if fee_bps.is_some_and(|rate| rate > types::MAX_PROTOCOL_FEE_BPS) {
+            panic!("Fee rate cannot exceed 1000 basis points");
+        }
+
+        storage::set_asset_fee_rate(&env, &asset, fee_bps);
+    }
+
+    /// Exempt a recipient from protocol fees, or lift the exemption. Admin-only.
+    pub fn set_fee_exempt(env: Env, account: Address, exempt: bool) {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_fee_exempt(&env, &account, exempt);
+    }
+
+    /// Fees collected in `asset` and not yet withdrawn
+    pub fn get_collected_fees(env: Env, asset: Address) -> i128 {
+        storage::get_collected_fees(&env, &asset)
+    }
+
+    /// Send collected fees to the treasury. Treasurer-only.
+    pub fn withdraw_fees(env: Env, caller: Address, asset: Address, amount: i128) -> Result<(), Error> {
+        Self::require_role_internal(&env, &caller, types::Role::Treasurer);
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let collected = storage::get_collected_fees(&env, &asset);
+        if amount > collected {
+            return Err(Error::NoFundsAvailable);
+        }
+
+        let config = storage::get_fee_config(&env).expect("Fees are not configured");
+        let token_client = TokenClient::new(&env, &asset);
+        token_client.transfer(&env.current_contract_address(), &config.treasury, &amount);
+        storage::set_collected_fees(&env, &asset, collected - amount);
+
+        events::emit_fees_withdrawn(&env, asset, config.treasury, amount);
+
+        Ok(())
+    }
+
+    /// Fees, per asset, that releasing everything the escrow holds now would take
+    pub fn get_fee_quote(env: Env, split_id_str: String) -> Result<Map<Address, i128>, Error> {
+        let escrow = storage::get_escrow(&env, &split_id_str).ok_or(Error::SplitNotFound)?;
+
+        let late_fees = escrow.late_fees_by_asset(&env);
+        let mut quote = Map::new(&env);
+        for (asset_address, asset_amount) in escrow.unreleased_by_asset(&env).iter() {
+            let penalty = late_fees.get(asset_address.clone()).unwrap_or(0).min(asset_amount);
+            let mut fee: i128 = 0;
+            for (recipient, amount) in escrow.payout_plan(&env, asset_amount - penalty).iter() {
+                fee += Self::payout_fee_internal(&env, &asset_address, &recipient, amount);
+            }
+            quote.set(asset_address, fee);
+        }
+
+        Ok(quote)
+    }
+
+    // ============================================
+    // Cross-Chain Bridge
+    // ============================================
+
+    /// Set the relayer keys, refund timeout and vault. Admin-only.
+    pub fn set_bridge_config(env: Env, config: types::BridgeConfig) {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if config.relayers.is_empty() {
+            panic!("At least one relayer is required");
+        }
+
+        storage::set_bridge_config(&env, &config);
+    }
+
+    /// Lock tokens to be bridged to `recipient` on another chain
+    pub fn initiate_bridge(
+        env: Env,
+        sender: Address,
+        asset: Address,
+        amount: i128,
+        source_chain: String,
+        destination_chain: String,
+        recipient: String,
+    ) -> Result<String, Error> {
+        sender.require_auth();
+
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
+            panic!("Contract is paused");
+        }
+
+        let config = storage::get_bridge_config(&env).expect("Bridge is not configured");
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if !storage::is_asset_approved(&env, &asset) {
+            return Err(Error::AssetNotApproved);
+        }
+
+        let bridge_id = storage::get_next_bridge_id(&env);
+        if storage::has_bridge_transaction(&env, &bridge_id) {
+            return Err(Error::BridgeAlreadyExists);
+        }
+
+        let token_client = TokenClient::new(&env, &asset);
+        token_client.transfer(&sender, &env.current_contract_address(), &amount);
+
+        let now = env.ledger().timestamp();
+        let bridge = types::BridgeTransaction {
+            bridge_id: bridge_id.clone(),
+            source_chain: source_chain.clone(),
+            destination_chain: destination_chain.clone(),
+            asset,
+            amount,
+            recipient: recipient.clone(),
+            sender,
+            created_at: now,
+            status: types::BridgeStatus::Initiated,
+            proof_hash: None,
+            completed_at: None,
+            refund_after: now + config.refund_timeout,
+        };
+        storage::set_bridge_transaction(&env, &bridge_id, &bridge);
+
+        events::emit_bridge_initiated(&env, &bridge_id, &source_chain, &destination_chain, amount, &recipient);
+
+        Ok(bridge_id)
+    }
+
+    /// Complete cross-chain bridge transaction
+    ///
+    /// `signature` must be a configured relayer's ed25519 signature over
+    /// the XDR encoding of ("sebridge", network ID, this contract's
+    /// address, bridge_id, amount, recipient), so it can't be replayed on
+    /// another deployment or network. An invalid signature aborts the call.
+    /// The locked tokens move to the vault. Once `refund_after` has passed
+    /// the bridge can only be refunded.
+    pub fn complete_bridge(
+        env: Env,
+        bridge_id: String,
+        relayer: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<(), Error> {
+        let config = storage::get_bridge_config(&env).expect("Bridge is not configured");
+
+        // Get bridge transaction
+        let mut bridge = storage::get_bridge_transaction(&env, &bridge_id)
+            .ok_or(Error::BridgeNotFound)?;
+
+        // Check if bridge is still initiated
+        if bridge.status != types::BridgeStatus::Initiated {
+            return Err(Error::InvalidBridgeStatus);
+        }
+        if env.ledger().timestamp() >= bridge.refund_after {
+            return Err(Error::InvalidBridgeStatus);
+        }
+
+        if !config.relayers.contains(&relayer) {
+            return Err(Error::RelayerNotAuthorized);
+        }
+
+        let message = Self::bridge_message(&env, &bridge);
+        env.crypto().ed25519_verify(&relayer, &message, &signature);
+
+        let token_client = TokenClient::new(&env, &bridge.asset);
+        token_client.transfer(&env.current_contract_address(), &config.vault, &bridge.amount);
+
+        // Update bridge transaction
+        bridge.status = types::BridgeStatus::Completed;
+        bridge.proof_hash = Some(env.crypto().sha256(&Bytes::from(signature)).to_bytes().into());
+        bridge.completed_at = Some(env.ledger().timestamp());
+
+        // Store updated bridge transaction
+        storage::set_bridge_transaction(&env, &bridge_id, &bridge);
+
+        // Emit bridge completed event
+        events::emit_bridge_completed(&env, &bridge_id, &bridge.recipient);
+
+        Ok(())
+    }
+
+    /// Return locked tokens to the sender of a bridge that timed out
+    ///
+    /// Anyone can trigger this once `refund_after` has passed; the tokens
+    /// only ever go back to the sender.
+    pub fn refund_bridge(env: Env, bridge_id: String) -> Result<(), Error> {
+        let mut bridge = storage::get_bridge_transaction(&env, &bridge_id)
+            .ok_or(Error::BridgeNotFound)?;
+
+        if bridge.status != types::BridgeStatus::Initiated {
+            return Err(Error::InvalidBridgeStatus);
+        }
+        if env.ledger().timestamp() < bridge.refund_after {
+            return Err(Error::InvalidBridgeStatus);
+        }
+
+        let token_client = TokenClient::new(&env, &bridge.asset);
+        token_client.transfer(&env.current_contract_address(), &bridge.sender, &bridge.amount);
+
+        bridge.status = types::BridgeStatus::Refunded;
+        bridge.completed_at = Some(env.ledger().timestamp());
+        storage::set_bridge_transaction(&env, &bridge_id, &bridge);
+
+        events::emit_bridge_refunded(&env, &bridge_id, &bridge.sender);
+
+        Ok(())
+    }
+
+    /// Get a bridge transaction by ID
+    pub fn get_bridge(env: Env, bridge_id: String) -> Result<types::BridgeTransaction, Error> {
+        storage::get_bridge_transaction(&env, &bridge_id).ok_or(Error::BridgeNotFound)
+    }
+
+    /// Bytes a relayer signs to confirm a bridge
+    ///
+    /// Bound to this network and contract as well as the bridge itself.
+    fn bridge_message(env: &Env, bridge: &types::BridgeTransaction) -> Bytes {
+        (
+            symbol_short!("sebridge"),
+            env.ledger().network_id(),
+            env.current_contract_address(),
+            bridge.bridge_id.clone(),
+            bridge.amount,
+            bridge.recipient.clone(),
+        )
+            .to_xdr(env)
+    }
+
+    /// Protocol fee on a payout of `amount` in `asset` to `recipient`
+    fn payout_fee_internal(env: &Env, asset: &Address, recipient: &Address, amount: i128) -> i128 {
+        let config = match storage::get_fee_config(env) {
+            Some(config) => config,
+            None => return 0,
+        };
+        if storage::is_fee_exempt(env, recipient) {
+            return 0;
+        }
+
+        let rate = storage::get_asset_fee_rate(env, asset).unwrap_or(config.fee_bps);
+        config.fee_for(rate, amount)
+    }
+
+    /// Transfer a payout, withholding the protocol fee
+    ///
+    /// The fee stays in the contract and is added to the asset's collected
+    /// fees. Returns the fee taken.
+    fn pay_out_internal(env: &Env, split_id: &String, asset: &Address, recipient: &Address, amount: i128) -> i128 {
+        let fee = Self::payout_fee_internal(env, asset, recipient, amount);
+        let net = amount - fee;
+
+        if net > 0 {
+            let token_client = TokenClient::new(env, asset);
+            token_client.transfer(&env.current_contract_address(), recipient, &net);
+        }
+        if fee > 0 {
+            storage::set_collected_fees(env, asset, storage::get_collected_fees(env, asset) + fee);
+        }
+
+        events::emit_funds_released(env, split_id, recipient.clone(), net, fee);
+        fee
+    }
+
+    /// Pay the keeper bounty from collected default-token fees
+    ///
+    /// Capped at a fraction of `refunded`, so sweeping empty escrows earns
+    /// nothing. Skipped when no bounty is configured or the fees can't
+    /// cover it.
+    fn pay_keeper_bounty_internal(env: &Env, keeper: &Address, refunded: i128) {
+        let configured = match storage::get_fee_config(env) {
+            Some(config) if config.keeper_bounty > 0 => config.keeper_bounty,
+            _ => return,
+        };
+
+        let bounty = configured.min(refunded * types::MAX_KEEPER_BOUNTY_BPS as i128 / types::MAX_BASIS_POINTS as i128);
+        if bounty <= 0 {
+            return;
+        }
+
+        let token = storage::get_token(env);
+        let collected = storage::get_collected_fees(env, &token);
+        if collected < bounty {
+            return;
+        }
+
+        let token_client = TokenClient::new(env, &token);
+        token_client.transfer(&env.current_contract_address(), keeper, &bounty);
+        storage::set_collected_fees(env, &token, collected - bounty);
+    }
+
+    /// Require `caller`'s authorisation and that it holds `role`
+    fn require_role_internal(env: &Env, caller: &Address, role: types::Role) {
+        if admin::require_role(env, caller, role).is_err() {
+            panic!("Caller does not hold the required role");
+        }
+    }
+
+    /// Internal helper function to release funds
+    fn release_funds_internal(env: &Env, split_id_str: String, mut escrow: SplitEscrow) -> Result<i128, Error> {
+        if escrow.status == EscrowStatus::Cancelled {
+            return Err(Error::SplitCancelled);
+        }
+
+        Self::check_release_allowed_internal(env, &split_id_str, &escrow)?;
+
+        let total_amount = escrow.total_amount;
+        let contract_address = env.current_contract_address();
+
+        // Pay out each asset separately, routing it across the configured
+        // payees (creator by default). Anything already paid out through
+        // release_partial is excluded by the ledger.
+        let held = escrow.unreleased_by_asset(env);
+        let late_fees = escrow.late_fees_by_asset(env);
+        for (asset_address, asset_amount) in held.iter() {
+            let token_client = TokenClient::new(env, &asset_address);
+
+            // Late penalties go to the treasury (or creator) before payees
+            let mut penalty = late_fees.get(asset_address.clone()).unwrap_or(0);
+            if penalty > asset_amount {
+                penalty = asset_amount;
+            }
+            if penalty > 0 {
+                let penalty_recipient = escrow
+                    .late_policy()
+                    .and_then(|policy| policy.treasury)
+                    .unwrap_or(escrow.creator.clone());
+                token_client.transfer(&contract_address, &penalty_recipient, &penalty);
+                events::emit_funds_released(env, &split_id_str, penalty_recipient, penalty, 0);
+            }
+
+            let plan = escrow.payout_plan(env, asset_amount - penalty);
+            for (recipient, amount) in plan.iter() {
+                Self::pay_out_internal(env, &split_id_str, &asset_address, &recipient, amount);
+            }
+            escrow.record_asset_release(&asset_address, asset_amount);
+        }
+
+        escrow.status = EscrowStatus::Released;
+        storage::set_escrow(env, &split_id_str, &escrow);
+
+        Self::record_settlement_activity_internal(env, &split_id_str, &escrow);
+
+        Ok(total_amount)
+    }
+
+    /// Apply an approved amendment and rebalance what's already been paid
+    ///
+    /// Participants who keep their asset carry their payment over, with
+    /// anything above their new share refunded. Removed participants and
+    /// anyone switching asset are refunded in full.
+    fn apply_amendment_internal(
+        env: &Env,
+        split_id_str: String,
+        mut escrow: SplitEscrow,
+        amendment: types::Amendment,
+    ) {
+        let now = env.ledger().timestamp();
+        let mut participants = Vec::new(env);
+        let mut total_amount: i128 = 0;
+
+        for i in 0..amendment.shares.len() {
+            let share = amendment.shares.get(i).unwrap();
+            let p = match escrow.find_participant(&share.address) {
+                Some(mut p) if p.asset == share.asset => {
+                    p.amount_owed = share.amount_owed + p.late_fee;
+                    let excess = p.amount_paid - p.amount_owed;
+                    if excess > 0 {
+                        Self::refund_amount_internal(env, &mut escrow, &p, excess, excess);
+                        p.amount_paid = p.amount_owed;
+                    }
+                    p.paid_at = if p.has_fully_paid() { Some(p.paid_at.unwrap_or(now)) } else { None };
+                    p
+                }
+                Some(old) => {
+                    if old.amount_paid > 0 {
+                        Self::refund_amount_internal(env, &mut escrow, &old, old.amount_paid, old.amount_paid);
+                    }
+                    let mut p = EscrowParticipant::new(share.address.clone(), share.asset.clone(), share.amount_owed);
+                    p.auto_collect = old.auto_collect;
+                    p
+                }
+                None => EscrowParticipant::new(share.address.clone(), share.asset.clone(), share.amount_owed),
+            };
+            total_amount += p.amount_owed;
+            participants.push_back(p);
+        }
+
+        // Anyone dropped from the split gets their money back
+        for i in 0..escrow.participants.len() {
+            let old = escrow.participants.get(i).unwrap();
+            let kept = amendment.shares.iter().any(|share| share.address == old.address);
+            if !kept && old.amount_paid > 0 {
+                Self::refund_amount_internal(env, &mut escrow, &old, old.amount_paid, old.amount_paid);
+            }
+        }
+
+        escrow.participants = participants;
+        escrow.total_amount = total_amount;
+        // Shares were set explicitly, so the original itemisation no longer applies
+        escrow.line_items = Vec::new(env);
+
+        if let Err(reason) = escrow.validate() {
+            panic!("{}", reason);
+        }
+
+        storage::set_escrow(env, &split_id_str, &escrow);
+        events::emit_amendment_applied(env, &split_id_str, total_amount);
+
+        Self::settle_if_funded_internal(env, split_id_str, escrow);
+    }
+
+    /// Credit rewards activity for an escrow that has paid out in full
+    ///
+    /// Nothing is credited while an escrow can still be cancelled or
+    /// refunded, so splits that never settle earn nothing. The creator is
+    /// credited with the split and its volume, and each participant with
+    /// what they paid in, net of anything already refunded to them.
+    fn record_settlement_activity_internal(env: &Env, split_id_str: &String, escrow: &SplitEscrow) {
+        Self::record_activity_internal(env, &escrow.creator, types::ActivityType::SplitCreated, split_id_str, escrow.total_amount);
+        Self::record_activity_internal(env, &escrow.creator, types::ActivityType::SplitCompleted, split_id_str, escrow.amount_collected);
+
+        for p in escrow.participants.iter() {
+            if p.amount_paid > 0 {
+                Self::record_activity_internal(env, &p.address, types::ActivityType::SplitParticipated, split_id_str, p.amount_paid);
+                Self::record_activity_internal(env, &p.address, types::ActivityType::DepositMade, split_id_str, p.amount_paid);
+            }
+        }
+    }
+
+    /// Record a user activity and update their rewards totals
+    ///
+    /// A settled split counts toward its creator's `total_splits_created`
+    /// and each paying participant's `total_splits_participated`; the
+    /// amounts settled count toward `total_amount_transacted`.
+    fn record_activity_internal(
+        env: &Env,
+        user: &Address,
+        activity_type: types::ActivityType,
+        split_id_str: &String,
+        amount: i128,
+    ) {
+        let now = env.ledger().timestamp();
+        let mut rewards = storage::get_user_rewards(env, user).unwrap_or(types::UserRewards {
+            user: user.clone(),
+            total_splits_created: 0,
+            total_splits_participated: 0,
+            total_amount_transacted: 0,
+            rewards_earned: 0,
+            rewards_claimed: 0,
+            last_activity: now,
+            status: types::RewardsStatus::Active,
+        });
+
+        match activity_type {
+            types::ActivityType::SplitCreated => rewards.total_splits_created += 1,
+            types::ActivityType::SplitParticipated => rewards.total_splits_participated += 1,
+            types::ActivityType::DepositMade | types::ActivityType::SplitCompleted => {
+                rewards.total_amount_transacted += amount
+            }
+        }
+        rewards.last_activity = now;
+        storage::set_user_rewards(env, user, &rewards);
+
+        let activity_id = storage::get_next_activity_id(env);
+        let activity = types::UserActivity {
+            user: user.clone(),
+            activity_type,
+            split_id: split_id_str.clone(),
+            amount,
+            timestamp: now,
+        };
+        storage::set_user_activity(env, user, activity_id, &activity);
+
+        events::emit_activity_tracked(env, user, activity_type, split_id_str, amount);
+    }
+
+    /// Unwind part of a participant's payments and refund whoever paid them
+    ///
+    /// `unwound` of the participant's `amount_paid` is reversed and
+    /// `refund_amount` of it returned, split across payers by the payment
+    /// log. Updates the escrow's ledger but not the participant record;
+    /// the caller adjusts `amount_paid` and persists the escrow.
+    fn refund_amount_internal(
+        env: &Env,
+        escrow: &mut SplitEscrow,
+        participant: &EscrowParticipant,
+        unwound: i128,
+        refund_amount: i128,
+    ) {
+        let log = storage::get_payment_log(env, &escrow.split_id);
+        let plan = types::payer_refunds(
+            env,
+            &log,
+            &participant.address,
+            participant.amount_paid,
+            unwound,
+            refund_amount,
+        );
+
+        let token_client = TokenClient::new(env, &participant.asset);
+        let contract_address = env.current_contract_address();
+        for (payer, unwound_share, refund_share) in plan.iter() {
+            if refund_share > 0 {
+                token_client.transfer(&contract_address, &payer, &refund_share);
+                events::emit_refund_issued(env, &escrow.split_id, payer.clone(), refund_share);
+            }
+            storage::add_payment_record(
+                env,
+                &escrow.split_id,
+                &types::PaymentRecord {
+                    payer,
+                    beneficiary: participant.address.clone(),
+                    amount: -unwound_share,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        escrow.amount_collected -= unwound;
+        escrow.record_asset_refund(&participant.asset, unwound, refund_amount);
+    }
+
+    /// Record who paid toward whose share
+    fn log_payment_internal(env: &Env, split_id_str: &String, payer: &Address, beneficiary: &Address, amount: i128) {
+        storage::add_payment_record(
+            env,
+            split_id_str,
+            &types::PaymentRecord {
+                payer: payer.clone(),
+                beneficiary: beneficiary.clone(),
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Credit a payment to a participant's share
+    ///
+    /// Checks the escrow is open, brings any late surcharge up to date,
+    /// and applies the escrow's overpayment policy. Updates the escrow in
+    /// place (the caller moves the tokens and persists it) and returns the
+    /// participant's asset and the amount actually credited.
+    fn record_payment_internal(
+        env: &Env,
+        escrow: &mut SplitEscrow,
+        participant: &Address,
+        amount: i128,
+    ) -> (Address, i128) {
+        let now = env.ledger().timestamp();
+
+        if escrow.is_expired(now) {
+            escrow.status = EscrowStatus::Expired;
+        }
+
+        if amount <= 0 {
+            panic!("Deposit amount must be positive");
+        }
+
+        if escrow.status == EscrowStatus::Expired {
+            panic!("Escrow has expired");
+        }
+
+        if escrow.status != EscrowStatus::Active {
+            panic!("Escrow is not active");
+        }
+
+        // Find the participant in the split and capture their asset
+        let mut found = false;
+        let mut participant_asset: Option<Address> = None;
+        let mut surcharge: i128 = 0;
+        let mut accepted: i128 = 0;
+        let mut updated_participants = Vec::new(env);
+
+        for i in 0..escrow.participants.len() {
+            let mut p = escrow.participants.get(i).unwrap();
+            if p.address == *participant {
+                found = true;
+                // Inside the grace period the late surcharge is added first
+                surcharge = escrow.apply_late_surcharge(&mut p, now);
+                let remaining = p.amount_owed - p.amount_paid;
+
+                accepted = amount;
+                if amount > remaining {
+                    if escrow.overpayment_policy == types::OverpaymentPolicy::Reject || remaining <= 0 {
+                        panic!("Deposit exceeds remaining amount owed");
+                    }
+                    accepted = remaining;
+                }
+                // Capture this participant's asset for transfer
+                participant_asset = Some(p.asset.clone());
+
+                p.amount_paid += accepted;
+                if p.amount_paid >= p.amount_owed {
+                    p.paid_at = Some(now);
+                }
+            }
+            updated_participants.push_back(p);
+        }
+
+        if !found {
+            panic!("Participant not found in escrow");
+        }
+
+        let asset_address = participant_asset.expect("Asset not set");
+
+        escrow.total_amount += surcharge;
+        escrow.participants = updated_participants;
+        escrow.amount_collected += accepted;
+        escrow.record_asset_deposit(&asset_address, accepted);
+
+        (asset_address, accepted)
+    }
+
+    /// Release a fully funded escrow, or park it for claim_vested
+    fn settle_if_funded_internal(env: &Env, split_id_str: String, mut escrow: SplitEscrow) {
+        if !escrow.is_fully_funded() {
+            return;
+        }
+
+        if escrow.vesting().is_some()
+            || Self::check_release_allowed_internal(env, &split_id_str, &escrow).is_err()
+            || storage::is_paused(env, types::PauseGroup::Releases)
+        {
+            // Streaming escrows stay put and pay out through claim_vested;
+            // flagged escrows wait for their receipt to be verified, and
+            // paused releases wait for release_funds after unpausing
+            escrow.status = EscrowStatus::Completed;
+            storage::set_escrow(env, &split_id_str, &escrow);
+        } else {
+            let _ = Self::release_funds_internal(env, split_id_str, escrow);
+        }
+    }
+
+    /// Pay `amount` from the premium pool to a policy holder
+    ///
+    /// Updates and persists the policy; it is marked Claimed once its
+    /// coverage is used up.
+    fn pay_insurance_internal(
+        env: &Env,
+        policy: &mut types::InsurancePolicy,
+        claim_id: &String,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let pool = storage::get_premium_pool(env);
+        if amount > pool {
+            return Err(Error::NoFundsAvailable);
+        }
+
+        if amount > 0 {
+            let token_client = TokenClient::new(env, &storage::get_token(env));
+            token_client.transfer(&env.current_contract_address(), &policy.policy_holder, &amount);
+            storage::set_premium_pool(env, pool - amount);
+            events::emit_payout_made(env, claim_id, &policy.policy_holder, amount);
+        }
+
+        policy.paid_out += amount;
+        if policy.remaining_coverage() == 0 {
+            policy.status = types::InsuranceStatus::Claimed;
+        }
+        storage::set_insurance(env, &policy.insurance_id, policy);
+
+        Ok(())
+    }
+
+    /// Fail if the escrow's receipt is awaiting verification or was rejected
+    ///
+    /// A request that has timed out no longer blocks, whether or not
+    /// `expire_verification` has been called.
+    fn check_verification_internal(env: &Env, split_id_str: &String) -> Result<(), Error> {
+        let verification_id = match storage::get_escrow_verification(env, split_id_str) {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+        let request = storage::get_verification_request(env, &verification_id).unwrap();
+
+        match request.status {
+            types::VerificationStatus::Pending => {
+                let timeout = storage::get_oracle_config(env).map_or(0, |config| config.verification_timeout);
+                if request.is_timed_out(timeout, env.ledger().timestamp()) {
+                    Ok(())
+                } else {
+                    Err(Error::VerificationPending)
+                }
+            }
+            types::VerificationStatus::Rejected => Err(Error::VerificationRejected),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether the escrow's funds can be paid out right now
+    ///
+    /// Releases wait on receipt verification, on disputes and, for gated
+    /// escrows, on multisig approval.
+    fn check_release_allowed_internal(env: &Env, split_id_str: &String, escrow: &SplitEscrow) -> Result<(), Error> {
+        Self::check_verification_internal(env, split_id_str)?;
+        Self::check_dispute_internal(env, split_id_str)?;
+        if escrow.multisig_gated {
+            Self::check_multisig_internal(env, split_id_str, escrow)?;
+        }
+        Ok(())
+    }
+
+    /// Require the multisig contract to have approved the split
+    ///
+    /// Approval means a majority of the escrow's own participants signed
+    /// the split. Anyone can create a split under the escrow's ID and pick
+    /// its threshold and time lock, so neither is consulted; only the
+    /// escrow's quorum counts, and signers outside the escrow are ignored.
+    fn check_multisig_internal(env: &Env, split_id_str: &String, escrow: &SplitEscrow) -> Result<(), Error> {
+        let contract = storage::get_multisig_contract(env).expect("Multisig contract is not configured");
+        let client = multisig::MultisigClient::new(env, &contract);
+
+        match client.get_split_status(split_id_str) {
+            None => return Err(Error::MultisigNotApproved),
+            Some(multisig::MultisigStatus::Cancelled) => return Err(Error::MultisigCancelled),
+            Some(_) => {}
+        }
+
+        // The multisig contract already refuses a second signature from a signer
+        let split = client.get_split_info(split_id_str);
+        let approvals = split
+            .signers
+            .iter()
+            .filter(|signer| escrow.find_participant(signer).is_some())
+            .count() as u32;
+        if approvals < escrow.multisig_quorum() {
+            return Err(Error::MultisigNotApproved);
+        }
+
+        Ok(())
+    }
+
+    /// Block releases while a dispute opened through the escrow is live
+    ///
+    /// A dispute in voting holds the funds; one upheld for the raiser means
+    /// the escrow should be refunded through `settle_dispute` instead.
+    fn check_dispute_internal(env: &Env, split_id_str: &String) -> Result<(), Error> {
+        let dispute_ids = storage::get_escrow_disputes(env, split_id_str);
+        if dispute_ids.is_empty() {
+            return Ok(());
+        }
+
+        let contract = storage::get_dispute_contract(env).expect("Dispute contract is not configured");
+        let client = dispute::DisputeClient::new(env, &contract);
+        let mut voting = false;
+        for dispute_id in dispute_ids.iter() {
+            let outcome = client.get_dispute(&dispute_id);
+            if outcome.result == Some(dispute::DisputeResult::UpheldForRaiser) {
+                return Err(Error::DisputeUpheld);
+            }
+            voting |= outcome.status == dispute::DisputeStatus::Voting;
+        }
+
+        if voting {
+            return Err(Error::DisputeOpen);
+        }
+        Ok(())
+    }
+
+    /// Refund every participant their refundable share
+    ///
+    /// Updates the escrow's ledger and payment records in place; the
+    /// caller persists it. Returns the total amount refunded.
+    fn refund_all_internal(env: &Env, escrow: &mut SplitEscrow) -> i128 {
+        let mut updated_participants = Vec::new(env);
+        let mut total_refunded: i128 = 0;
+
+        for i in 0..escrow.participants.len() {
+            let mut p = escrow.participants.get(i).unwrap();
+            if p.amount_paid > 0 {
+                let refund_amount = escrow.refundable_amount(&p.asset, p.amount_paid);
+                Self::refund_amount_internal(env, escrow, &p, p.amount_paid, refund_amount);
+                total_refunded += refund_amount;
+
+                p.amount_paid = 0;
+                p.paid_at = None;
+            }
+            updated_participants.push_back(p);
+        }
+
+        escrow.participants = updated_participants;
+        total_refunded
+    }
+
+    /// Transfer vested-but-unclaimed funds to the creator
+    ///
+    /// Updates the escrow's ledger in place; the caller persists it.
+    fn pay_vested_internal(env: &Env, escrow: &mut SplitEscrow) -> i128 {
+        let claimable = escrow.vested_unreleased_by_asset(env, env.ledger().timestamp());
+        let mut paid: i128 = 0;
+
+        for (asset_address, amount) in claimable.iter() {
+            Self::pay_out_internal(env, &escrow.split_id, &asset_address, &escrow.creator, amount);
+            escrow.record_asset_release(&asset_address, amount);
+            paid += amount;
+        }
+
+        paid
+    }
+
+    // ============================================
+    // Atomic Swaps
+    // ============================================
+
+    /// Open a hash-locked swap and escrow participant A's leg
+    ///
+    /// `hash_lock` is the SHA-256 hash of a secret known to A. The swap
+    /// becomes claimable once B escrows their leg with `accept_swap`, and
+    /// must be claimed before `time_lock`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_swap(
+        env: Env,
+        participant_a: Address,
+        participant_b: Address,
+        asset_a: Address,
+        amount_a: i128,
+        asset_b: Address,
+        amount_b: i128,
+        hash_lock: BytesN<32>,
+        time_lock: u64,
+    ) -> Result<String, Error> {
+        participant_a.require_auth();
+
+        if storage::is_paused(&env, types::PauseGroup::Deposits) {
+            panic!("Contract is paused");
+        }
+
+        if amount_a <= 0 || amount_b <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if !storage::is_asset_approved(&env, &asset_a) || !storage::is_asset_approved(&env, &asset_b) {
+            return Err(Error::AssetNotApproved);
+        }
+
+        let now = env.ledger().timestamp();
+        if time_lock <= now {
+            return Err(Error::SwapExpired);
+        }
+
+        let swap_id = storage::get_next_swap_id(&env);
+        if storage::has_atomic_swap(&env, &swap_id) {
+            return Err(Error::SwapAlreadyExists);
+        }
+
+        let token_client = TokenClient::new(&env, &asset_a);
+        token_client.transfer(&participant_a, &env.current_contract_address(), &amount_a);
+
+        let swap = types::AtomicSwap {
+            swap_id: swap_id.clone(),
+            participant_a: participant_a.clone(),
+            participant_b: participant_b.clone(),
+            asset_a,
+            asset_b,
+            amount_a,
+            amount_b,
+            hash_lock,
+            secret: None,
+            time_lock,
+            created_at: now,
+            status: types::SwapStatus::Pending,
+            completed_at: None,
+        };
+        storage::set_atomic_swap(&env, &swap_id, &swap);
+
+        events::emit_swap_created(&env, &swap_id, &participant_a, &participant_b, amount_a, amount_b);
+
+        Ok(swap_id)
+    }
+
+    /// Escrow participant B's leg, making the swap claimable
+    pub fn accept_swap(env: Env, swap_id: String) -> Result<(), Error> {
+        let mut swap = storage::get_atomic_swap(&env, &swap_id).ok_or(Error::SwapNotFound)?;
+        swap.participant_b.require_auth();
+
+        if swap.status != types::SwapStatus::Pending {
+            return Err(Error::InvalidSwapStatus);
+        }
+        if env.ledger().timestamp() >= swap.time_lock {
+            return Err(Error::SwapExpired);
+        }
+
+        let token_client = TokenClient::new(&env, &swap.asset_b);
+        token_client.transfer(&swap.participant_b, &env.current_contract_address(), &swap.amount_b);
+
+        swap.status = types::SwapStatus::Locked;
+        storage::set_atomic_swap(&env, &swap_id, &swap);
+
+        events::emit_swap_locked(&env, &swap_id, &swap.participant_b);
+
+        Ok(())
+    }
+
+    /// Complete a swap by revealing the secret
+    ///
+    /// Anyone holding the secret may settle the swap; A receives B's leg
+    /// and B receives A's leg. The secret is stored so it is public once
+    /// the swap completes.
+    pub fn claim_swap(env: Env, swap_id: String, secret: Bytes) -> Result<(), Error> {
+        let mut swap = storage::get_atomic_swap(&env, &swap_id).ok_or(Error::SwapNotFound)?;
+        if swap.status != types::SwapStatus::Locked {
+            return Err(Error::InvalidSwapStatus);
+        }
+
+        let now = env.ledger().timestamp();
+        if now >= swap.time_lock {
+            return Err(Error::SwapExpired);
+        }
+        if Self::hash_secret(&env, &secret) != swap.hash_lock {
+            return Err(Error::SecretInvalid);
+        }
+
+        let contract_address = env.current_contract_address();
+        TokenClient::new(&env, &swap.asset_a).transfer(&contract_address, &swap.participant_b, &swap.amount_a);
+        TokenClient::new(&env, &swap.asset_b).transfer(&contract_address, &swap.participant_a, &swap.amount_b);
+
+        swap.secret = Some(secret);
+        swap.status = types::SwapStatus::Completed;
+        swap.completed_at = Some(now);
+        storage::set_atomic_swap(&env, &swap_id, &swap);
+
+        events::emit_swap_executed(&env, &swap_id);
+
+        Ok(())
+    }
+
+    /// Return escrowed legs to their owners once the time lock has passed
+    pub fn refund_swap(env: Env, swap_id: String, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut swap = storage::get_atomic_swap(&env, &swap_id).ok_or(Error::SwapNotFound)?;
+        if swap.status != types::SwapStatus::Pending && swap.status != types::SwapStatus::Locked {
+            return Err(Error::InvalidSwapStatus);
+        }
+
+        let now = env.ledger().timestamp();
+        if now < swap.time_lock {
+            return Err(Error::InvalidSwapStatus);
+        }
+
+        let contract_address = env.current_contract_address();
+        TokenClient::new(&env, &swap.asset_a).transfer(&contract_address, &swap.participant_a, &swap.amount_a);
+        if swap.status == types::SwapStatus::Locked {
+            TokenClient::new(&env, &swap.asset_b).transfer(&contract_address, &swap.participant_b, &swap.amount_b);
+        }
+
+        swap.status = types::SwapStatus::Refunded;
+        swap.completed_at = Some(now);
+        storage::set_atomic_swap(&env, &swap_id, &swap);
+
+        events::emit_swap_refunded(&env, &swap_id, &caller);
+
+        Ok(())
+    }
+
+    /// Get an atomic swap by ID
+    pub fn get_swap(env: Env, swap_id: String) -> Result<types::AtomicSwap, Error> {
+        storage::get_atomic_swap(&env, &swap_id).ok_or(Error::SwapNotFound)
+    }
+
+    /// SHA-256 hash of a swap secret
+    fn hash_secret(env: &Env, secret: &Bytes) -> BytesN<32> {
+        env.crypto().sha256(secret).to_bytes()
+    }
+
+    pub fn get_split(env: Env, split_id_str: String) -> SplitEscrow {
+        let mut escrow = storage::get_escrow(&env, &split_id_str).expect("Escrow not found");
+        if escrow.is_expired(env.ledger().timestamp()) {
+            escrow.status = EscrowStatus::Expired;
+        }
+        escrow
+    }
+
+    /// List escrows opened by `creator`, oldest first
+    pub fn list_escrows_by_creator(env: Env, creator: Address, cursor: u32, limit: u32) -> types::EscrowPage {
+        Self::escrow_page_internal(&env, storage::EscrowIndex::Creator(creator), cursor, limit)
+    }
+
+    /// List escrows `participant` is part of, oldest first
+    pub fn list_escrows_by_participant(env: Env, participant: Address, cursor: u32, limit: u32) -> types::EscrowPage {
+        Self::escrow_page_internal(&env, storage::EscrowIndex::Participant(participant), cursor, limit)
+    }
+
+    /// List escrows by stored status, in the order they entered it
+    ///
+    /// Escrows past their deadline stay under Active until they are
+    /// finalised, though they are returned marked Expired like `get_split`.
+    pub fn list_escrows_by_status(env: Env, status: EscrowStatus, cursor: u32, limit: u32) -> types::EscrowPage {
+        Self::escrow_page_internal(&env, storage::EscrowIndex::Status(status), cursor, limit)
+    }
+
+    /// Read the escrows in `limit` index slots starting at slot `cursor`
+    ///
+    /// Slots emptied by removals are skipped, so a page can hold fewer
+    /// than `limit` escrows while more pages remain.
+    fn escrow_page_internal(env: &Env, index: storage::EscrowIndex, cursor: u32, limit: u32) -> types::EscrowPage {
+        if limit == 0 || limit > types::MAX_PAGE_SIZE {
+            panic!("Limit must be between 1 and 50");
+        }
+
+        let len = storage::get_escrow_index_len(env, &index);
+        let end = len.min(cursor.saturating_add(limit));
+        let now = env.ledger().timestamp();
+
+        let mut escrows = Vec::new(env);
+        for slot in cursor.min(end)..end {
+            let split_id = match storage::get_escrow_index_slot(env, &index, slot) {
+                Some(split_id) => split_id,
+                None => continue,
+            };
+            let mut escrow = storage::get_escrow(env, &split_id).expect("Escrow not found");
+            if escrow.is_expired(now) {
+                escrow.status = EscrowStatus::Expired;
+            }
+            escrows.push_back(escrow);
+        }
+
+        types::EscrowPage {
+            escrows,
+            next_cursor: if end < len { Some(end) } else { None },
+        }
+    }
+}
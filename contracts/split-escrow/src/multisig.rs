@@ -0,0 +1,40 @@
+//! # Multi-Signature Splits Interface
+//!
+//! Client for the `multi-sig-splits` contract. `MultisigStatus` and
+//! `MultisigSplit` mirror that contract's definitions so its return values
+//! decode here; keep them in step if the multisig contract changes.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, String, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MultisigStatus {
+    Pending,
+    Active,
+    Executed,
+    Cancelled,
+    Expired,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MultisigSplit {
+    pub split_id: String,
+    pub required_signatures: u32,
+    pub current_signatures: u32,
+    pub time_lock: u64,
+    pub created_at: u64,
+    pub executed_at: u64,
+    pub status: MultisigStatus,
+    pub signers: Vec<Address>,
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "MultisigClient")]
+pub trait MultisigInterface {
+    /// The split's status, or None if it doesn't exist
+    fn get_split_status(env: Env, split_id: String) -> Option<MultisigStatus>;
+
+    /// The split's signers and status; panics if it doesn't exist
+    fn get_split_info(env: Env, split_id: String) -> MultisigSplit;
+}
@@ -6,22 +6,10 @@
 //! This module includes both original storage patterns and the enhanced
 //! escrow storage keys as specified in issue #59.
 
-use soroban_sdk::{contracttype, Address, Env, String, symbol_short, Vec, Symbol};
+use soroban_sdk::{contracttype, Address, Env, String, Vec};
 use crate::types::*;
 
 
-const ADMIN: Symbol = symbol_short!("ADMIN");
-const INIT: Symbol = symbol_short!("INIT");
-
-pub fn is_initialized(env: &Env) -> bool {
-    env.storage().instance().has(&INIT)
-}
-
-pub fn set_initialized(env: &Env) {
-    env.storage().instance().set(&INIT, &true);
-}
-
-
 // ============================================
 // Original Storage Keys
 // ============================================
@@ -33,6 +21,12 @@ pub enum DataKey {
     /// The contract administrator
     Admin,
 
+    /// Nominee for the two-step admin transfer (temporary storage)
+    PendingAdmin,
+
+    /// Role membership flag for an account
+    Role(Role, Address),
+
     /// Counter for generating unique split IDs
     SplitCounter,
 
@@ -54,6 +48,23 @@ pub enum DataKey {
 // Enhanced Storage Keys (Issue #59)
 // ============================================
 
+/// A listing of escrows kept in storage
+///
+/// Each index is a run of numbered slots, one escrow per slot, so it never
+/// outgrows a ledger entry and removals don't move other entries.
+#[contracttype]
+#[derive(Clone)]
+pub enum EscrowIndex {
+    /// Escrows opened by a creator, oldest first
+    Creator(Address),
+
+    /// Escrows an address currently participates in, oldest first
+    Participant(Address),
+
+    /// Escrows currently stored with a status, in the order they entered it
+    Status(EscrowStatus),
+}
+
 /// Enhanced storage keys matching issue #59 specification
 ///
 /// I'm using a separate enum to clearly distinguish the new
@@ -72,6 +83,18 @@ pub enum StorageKey {
     /// Total number of escrows created
     EscrowCount,
 
+    /// Number of slots ever allocated in an escrow index
+    /// Maps: index -> u32
+    EscrowIndexLen(EscrowIndex),
+
+    /// One slot of an escrow index; removing an entry leaves a gap
+    /// Maps: (index, slot) -> split_id
+    EscrowIndexSlot(EscrowIndex, u32),
+
+    /// Slot an escrow currently holds in an index
+    /// Maps: (index, split_id) -> slot
+    EscrowIndexPosition(EscrowIndex, String),
+
     /// Admin address (shared with original)
     Admin,
 
@@ -89,14 +112,57 @@ pub enum StorageKey {
     /// Counter for generating unique claim IDs
     ClaimCounter,
 
+    /// Premium rate and claim window for new policies
+    InsuranceConfig,
+
+    /// Premiums (and top-ups) available to pay claims, in the default token
+    PremiumPool,
+
     /// Maps split_id to insurance_id (one-to-one)
     SplitToInsurance(String),
 
     /// Maps insurance_id to claim_id (one-to-many)
     InsuranceClaims(String),
 
-    /// Contract pause state
-    Paused,
+    /// Pause state of a group of entry points
+    Paused(PauseGroup),
+
+    /// Account allowed to pause (but not unpause) the contract
+    Guardian,
+
+    /// Dispute-resolution contract consulted before releases
+    DisputeContract,
+
+    /// Disputes opened through the escrow, oldest first
+    /// Maps: split_id -> Vec<dispute_id>
+    EscrowDisputes(String),
+
+    /// Disputes an account has raised against an escrow
+    /// Maps: (split_id, raiser) -> count
+    DisputesRaised(String, Address),
+
+    /// Multi-sig-splits contract that approves gated releases
+    MultisigContract,
+
+    /// Closed-cycle record for a recurring escrow
+    /// Maps: (split_id, cycle) -> CycleRecord
+    CycleRecord(String, u32),
+
+    /// Latest failed auto-collection for a participant
+    /// Maps: (split_id, participant_address) -> CollectionFailure
+    CollectionFailure(String, Address),
+
+    /// Pending participant amendment for an escrow (at most one)
+    /// Maps: split_id -> Amendment
+    Amendment(String),
+
+    /// Overpayment credit held for a user in a given asset
+    /// Maps: (owner_address, asset_address) -> i128 amount
+    Credit(Address, Address),
+
+    /// Who paid what toward each participant's share
+    /// Maps: split_id -> Vec<PaymentRecord>
+    PaymentLog(String),
 }
 
 /// Time-to-live for persistent storage (about 1 year)
@@ -121,20 +187,79 @@ pub fn set_admin(env: &Env, admin: &Address) {
     env.storage().instance().set(&DataKey::Admin, admin);
 }
 
+#[allow(dead_code)]
 pub fn is_initialized(env: &Env) -> bool {
     env.storage().instance().has(&DataKey::Initialized)
 }
 
+#[allow(dead_code)]
 pub fn set_initialized(env: &Env) {
     env.storage().instance().set(&DataKey::Initialized, &true);
 }
 
-pub fn is_paused(env: &Env) -> bool {
-    env.storage().instance().get(&StorageKey::Paused).unwrap_or(false)
+pub fn is_paused(env: &Env, group: PauseGroup) -> bool {
+    env.storage().instance().get(&StorageKey::Paused(group)).unwrap_or(false)
+}
+
+pub fn set_paused(env: &Env, group: PauseGroup, paused: bool) {
+    env.storage().instance().set(&StorageKey::Paused(group), &paused);
+}
+
+pub fn get_guardian(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&StorageKey::Guardian)
+}
+
+pub fn set_guardian(env: &Env, guardian: &Address) {
+    env.storage().instance().set(&StorageKey::Guardian, guardian);
+}
+
+pub fn get_dispute_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&StorageKey::DisputeContract)
 }
 
-pub fn set_paused(env: &Env, paused: bool) {
-    env.storage().instance().set(&StorageKey::Paused, &paused);
+pub fn set_dispute_contract(env: &Env, contract: &Address) {
+    env.storage().instance().set(&StorageKey::DisputeContract, contract);
+}
+
+/// IDs of the disputes opened against an escrow, oldest first
+pub fn get_escrow_disputes(env: &Env, split_id: &String) -> Vec<String> {
+    let key = StorageKey::EscrowDisputes(split_id.clone());
+    env.storage().persistent().get(&key).unwrap_or(Vec::new(env))
+}
+
+/// Record a dispute opened against an escrow
+pub fn add_escrow_dispute(env: &Env, split_id: &String, dispute_id: &String) {
+    let key = StorageKey::EscrowDisputes(split_id.clone());
+    let mut disputes = get_escrow_disputes(env, split_id);
+    disputes.push_back(dispute_id.clone());
+    env.storage().persistent().set(&key, &disputes);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
+/// Get how many disputes an account has raised against an escrow
+pub fn get_disputes_raised(env: &Env, split_id: &String, raiser: &Address) -> u32 {
+    let key = StorageKey::DisputesRaised(split_id.clone(), raiser.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Count another dispute from an account against an escrow
+pub fn increment_disputes_raised(env: &Env, split_id: &String, raiser: &Address) {
+    let key = StorageKey::DisputesRaised(split_id.clone(), raiser.clone());
+    let raised = get_disputes_raised(env, split_id, raiser);
+    env.storage().persistent().set(&key, &(raised + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
+pub fn get_multisig_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&StorageKey::MultisigContract)
+}
+
+pub fn set_multisig_contract(env: &Env, contract: &Address) {
+    env.storage().instance().set(&StorageKey::MultisigContract, contract);
 }
 
 // ============================================
@@ -142,6 +267,7 @@ pub fn set_paused(env: &Env, paused: bool) {
 // ============================================
 
 /// Check if the token has been set
+#[allow(dead_code)]
 pub fn has_token(env: &Env) -> bool {
     env.storage().persistent().has(&DataKey::Token)
 }
@@ -219,6 +345,7 @@ pub fn get_next_split_id(env: &Env) -> u64 {
 // ============================================
 
 /// Get a split by ID
+#[allow(dead_code)]
 pub fn get_split(env: &Env, split_id: u64) -> Split {
     let key = DataKey::Split(split_id);
     env.storage()
@@ -228,12 +355,14 @@ pub fn get_split(env: &Env, split_id: u64) -> Split {
 }
 
 /// Check if a split exists
+#[allow(dead_code)]
 pub fn has_split(env: &Env, split_id: u64) -> bool {
     let key = DataKey::Split(split_id);
     env.storage().persistent().has(&key)
 }
 
 /// Store a split
+#[allow(dead_code)]
 pub fn set_split(env: &Env, split_id: u64, split: &Split) {
     let key = DataKey::Split(split_id);
     env.storage().persistent().set(&key, split);
@@ -254,6 +383,7 @@ pub fn remove_split(env: &Env, split_id: u64) {
 // ============================================
 
 /// Get the total number of escrows created
+#[allow(dead_code)]
 pub fn get_escrow_count(env: &Env) -> u64 {
     env.storage()
         .persistent()
@@ -265,6 +395,7 @@ pub fn get_escrow_count(env: &Env) -> u64 {
 ///
 /// I'm using this to generate unique escrow IDs when string IDs
 /// are not provided externally.
+#[allow(dead_code)]
 pub fn increment_escrow_count(env: &Env) -> u64 {
     let current = get_escrow_count(env);
     let next = current + 1;
@@ -294,12 +425,99 @@ pub fn has_escrow(env: &Env, split_id: &String) -> bool {
 }
 
 /// Store an escrow
+///
+/// Also keeps the creator, participant and status indexes in step with
+/// the record and extends their TTL along with it.
 pub fn set_escrow(env: &Env, split_id: &String, escrow: &SplitEscrow) {
     let key = StorageKey::Escrow(split_id.clone());
+    let previous: Option<SplitEscrow> = env.storage().persistent().get(&key);
     env.storage().persistent().set(&key, escrow);
     env.storage()
         .persistent()
         .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+
+    update_escrow_indexes(env, split_id, previous.as_ref(), escrow);
+}
+
+// ============================================
+// Escrow Indexes
+// ============================================
+
+/// Number of slots allocated in an index, including emptied ones
+pub fn get_escrow_index_len(env: &Env, index: &EscrowIndex) -> u32 {
+    let key = StorageKey::EscrowIndexLen(index.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Split ID in an index slot, or None if the slot has been emptied
+pub fn get_escrow_index_slot(env: &Env, index: &EscrowIndex, slot: u32) -> Option<String> {
+    let key = StorageKey::EscrowIndexSlot(index.clone(), slot);
+    env.storage().persistent().get(&key)
+}
+
+fn extend_index_entry(env: &Env, key: &StorageKey) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
+/// Append an escrow to an index, or just extend its entries if it's listed
+fn add_to_escrow_index(env: &Env, index: &EscrowIndex, split_id: &String) {
+    let len_key = StorageKey::EscrowIndexLen(index.clone());
+    let position_key = StorageKey::EscrowIndexPosition(index.clone(), split_id.clone());
+
+    let slot = match env.storage().persistent().get::<_, u32>(&position_key) {
+        Some(slot) => slot,
+        None => {
+            let slot = get_escrow_index_len(env, index);
+            env.storage()
+                .persistent()
+                .set(&StorageKey::EscrowIndexSlot(index.clone(), slot), split_id);
+            env.storage().persistent().set(&position_key, &slot);
+            env.storage().persistent().set(&len_key, &(slot + 1));
+            slot
+        }
+    };
+
+    extend_index_entry(env, &len_key);
+    extend_index_entry(env, &position_key);
+    extend_index_entry(env, &StorageKey::EscrowIndexSlot(index.clone(), slot));
+}
+
+/// Empty an escrow's slot in an index; later slots keep their numbers
+fn remove_from_escrow_index(env: &Env, index: &EscrowIndex, split_id: &String) {
+    let position_key = StorageKey::EscrowIndexPosition(index.clone(), split_id.clone());
+    if let Some(slot) = env.storage().persistent().get::<_, u32>(&position_key) {
+        env.storage().persistent().remove(&position_key);
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::EscrowIndexSlot(index.clone(), slot));
+    }
+}
+
+/// Reconcile the indexes after an escrow write
+///
+/// Entries are only added or removed when the creator, a participant or
+/// the status actually changes; unchanged entries just have their TTL
+/// extended so an index never expires before the escrows it lists.
+fn update_escrow_indexes(env: &Env, split_id: &String, previous: Option<&SplitEscrow>, escrow: &SplitEscrow) {
+    add_to_escrow_index(env, &EscrowIndex::Creator(escrow.creator.clone()), split_id);
+
+    if let Some(prev) = previous {
+        if prev.status != escrow.status {
+            remove_from_escrow_index(env, &EscrowIndex::Status(prev.status.clone()), split_id);
+        }
+        for p in prev.participants.iter() {
+            if escrow.find_participant(&p.address).is_none() {
+                remove_from_escrow_index(env, &EscrowIndex::Participant(p.address.clone()), split_id);
+            }
+        }
+    }
+    add_to_escrow_index(env, &EscrowIndex::Status(escrow.status.clone()), split_id);
+
+    for p in escrow.participants.iter() {
+        add_to_escrow_index(env, &EscrowIndex::Participant(p.address.clone()), split_id);
+    }
 }
 
 /// Remove an escrow
@@ -309,6 +527,126 @@ pub fn remove_escrow(env: &Env, split_id: &String) {
     env.storage().persistent().remove(&key);
 }
 
+// ============================================
+// Recurring Escrow Cycle History
+// ============================================
+
+/// Get the record of a closed cycle
+pub fn get_cycle_record(env: &Env, split_id: &String, cycle: u32) -> Option<CycleRecord> {
+    let key = StorageKey::CycleRecord(split_id.clone(), cycle);
+    env.storage().persistent().get(&key)
+}
+
+/// Store the record of a closed cycle
+pub fn set_cycle_record(env: &Env, split_id: &String, record: &CycleRecord) {
+    let key = StorageKey::CycleRecord(split_id.clone(), record.cycle);
+    env.storage().persistent().set(&key, record);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
+// ============================================
+// Auto-Collection Failure Tracking
+// ============================================
+
+/// Get the latest failed auto-collection for a participant
+pub fn get_collection_failure(env: &Env, split_id: &String, participant: &Address) -> Option<CollectionFailure> {
+    let key = StorageKey::CollectionFailure(split_id.clone(), participant.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Record a failed auto-collection for a participant
+pub fn set_collection_failure(env: &Env, split_id: &String, failure: &CollectionFailure) {
+    let key = StorageKey::CollectionFailure(split_id.clone(), failure.participant.clone());
+    env.storage().persistent().set(&key, failure);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
+/// Clear a participant's failure record after a successful collection
+pub fn remove_collection_failure(env: &Env, split_id: &String, participant: &Address) {
+    let key = StorageKey::CollectionFailure(split_id.clone(), participant.clone());
+    env.storage().persistent().remove(&key);
+}
+
+// ============================================
+// Participant Amendment Storage
+// ============================================
+
+/// Get the pending amendment for an escrow
+pub fn get_amendment(env: &Env, split_id: &String) -> Option<Amendment> {
+    let key = StorageKey::Amendment(split_id.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Store the pending amendment for an escrow
+pub fn set_amendment(env: &Env, split_id: &String, amendment: &Amendment) {
+    let key = StorageKey::Amendment(split_id.clone());
+    env.storage().persistent().set(&key, amendment);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
+/// Remove the pending amendment once applied or withdrawn
+pub fn remove_amendment(env: &Env, split_id: &String) {
+    let key = StorageKey::Amendment(split_id.clone());
+    env.storage().persistent().remove(&key);
+}
+
+// ============================================
+// Overpayment Credit Storage
+// ============================================
+
+/// Get a user's credit balance in an asset
+pub fn get_credit(env: &Env, owner: &Address, asset: &Address) -> i128 {
+    let key = StorageKey::Credit(owner.clone(), asset.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set a user's credit balance in an asset (removes the entry at zero)
+pub fn set_credit(env: &Env, owner: &Address, asset: &Address, amount: i128) {
+    let key = StorageKey::Credit(owner.clone(), asset.clone());
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &amount);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
+/// Add to a user's credit balance and return the new balance
+pub fn add_credit(env: &Env, owner: &Address, asset: &Address, amount: i128) -> i128 {
+    let new_balance = get_credit(env, owner, asset) + amount;
+    set_credit(env, owner, asset, new_balance);
+    new_balance
+}
+
+// ============================================
+// Payment Log Storage
+// ============================================
+
+/// Get the payment log for an escrow
+pub fn get_payment_log(env: &Env, split_id: &String) -> Vec<PaymentRecord> {
+    let key = StorageKey::PaymentLog(split_id.clone());
+    env.storage().persistent().get(&key).unwrap_or(Vec::new(env))
+}
+
+/// Append an entry to an escrow's payment log
+pub fn add_payment_record(env: &Env, split_id: &String, record: &PaymentRecord) {
+    let key = StorageKey::PaymentLog(split_id.clone());
+    let mut log = get_payment_log(env, split_id);
+    log.push_back(record.clone());
+    env.storage().persistent().set(&key, &log);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
 // ============================================
 // Participant Payment Storage (Issue #59)
 // ============================================
@@ -317,12 +655,14 @@ pub fn remove_escrow(env: &Env, split_id: &String) {
 ///
 /// I'm tracking payments separately to allow efficient queries
 /// without loading the entire escrow structure.
+#[allow(dead_code)]
 pub fn get_participant_payment(env: &Env, split_id: &String, participant: &Address) -> i128 {
     let key = StorageKey::ParticipantPayment(split_id.clone(), participant.clone());
     env.storage().persistent().get(&key).unwrap_or(0)
 }
 
 /// Set the payment amount for a participant
+#[allow(dead_code)]
 pub fn set_participant_payment(env: &Env, split_id: &String, participant: &Address, amount: i128) {
     let key = StorageKey::ParticipantPayment(split_id.clone(), participant.clone());
     env.storage().persistent().set(&key, &amount);
@@ -335,6 +675,7 @@ pub fn set_participant_payment(env: &Env, split_id: &String, participant: &Addre
 ///
 /// I'm providing this helper to simplify the common case of
 /// adding a new payment to an existing balance.
+#[allow(dead_code)]
 pub fn add_participant_payment(
     env: &Env,
     split_id: &String,
@@ -348,6 +689,7 @@ pub fn add_participant_payment(
 }
 
 /// Check if a participant has any recorded payment
+#[allow(dead_code)]
 pub fn has_participant_payment(env: &Env, split_id: &String, participant: &Address) -> bool {
     let key = StorageKey::ParticipantPayment(split_id.clone(), participant.clone());
     env.storage().persistent().has(&key)
@@ -367,6 +709,7 @@ pub fn remove_participant_payment(env: &Env, split_id: &String, participant: &Ad
 /// Generate a unique escrow ID string
 ///
 /// I'm combining a counter with a prefix for readable IDs.
+#[allow(dead_code)]
 pub fn generate_escrow_id(env: &Env) -> String {
     let _count = increment_escrow_count(env);
     // Create a simple string ID like "escrow-1", "escrow-2", etc.
@@ -403,6 +746,30 @@ pub fn get_next_claim_id(env: &Env) -> u64 {
     next
 }
 
+/// Get the insurance configuration
+pub fn get_insurance_config(env: &Env) -> Option<InsuranceConfig> {
+    env.storage().instance().get(&StorageKey::InsuranceConfig)
+}
+
+/// Set the insurance configuration
+pub fn set_insurance_config(env: &Env, config: &InsuranceConfig) {
+    env.storage().instance().set(&StorageKey::InsuranceConfig, config);
+}
+
+/// Get the premium pool balance
+pub fn get_premium_pool(env: &Env) -> i128 {
+    env.storage().persistent().get(&StorageKey::PremiumPool).unwrap_or(0)
+}
+
+/// Set the premium pool balance
+pub fn set_premium_pool(env: &Env, balance: i128) {
+    let key = StorageKey::PremiumPool;
+    env.storage().persistent().set(&key, &balance);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
 /// Store an insurance policy
 pub fn set_insurance(env: &Env, insurance_id: &String, policy: &crate::types::InsurancePolicy) {
     let key = StorageKey::Insurance(insurance_id.clone());
@@ -428,6 +795,7 @@ pub fn has_insurance(env: &Env, insurance_id: &String) -> bool {
 }
 
 /// Remove an insurance policy
+#[allow(dead_code)]
 pub fn remove_insurance(env: &Env, insurance_id: &String) {
     let key = StorageKey::Insurance(insurance_id.clone());
     env.storage().persistent().remove(&key);
@@ -458,6 +826,7 @@ pub fn has_claim(env: &Env, claim_id: &String) -> bool {
 }
 
 /// Remove an insurance claim
+#[allow(dead_code)]
 pub fn remove_claim(env: &Env, claim_id: &String) {
     let key = StorageKey::Claim(claim_id.clone());
     env.storage().persistent().remove(&key);
@@ -485,6 +854,7 @@ pub fn has_split_insurance(env: &Env, split_id: &String) -> bool {
 }
 
 /// Remove split to insurance mapping
+#[allow(dead_code)]
 pub fn remove_split_to_insurance(env: &Env, split_id: &String) {
     let key = StorageKey::SplitToInsurance(split_id.clone());
     env.storage().persistent().remove(&key);
@@ -514,6 +884,9 @@ pub enum RewardsStorageKey {
     UserRewards(Address),
     UserActivity(Address, u64),
     ActivityCounter,
+    Config,
+    Treasury,
+    EpochEmitted(u64),
 }
 
 /// Get user rewards data
@@ -529,12 +902,14 @@ pub fn set_user_rewards(env: &Env, user: &Address, rewards: &UserRewards) {
 }
 
 /// Check if user has rewards data
+#[allow(dead_code)]
 pub fn has_user_rewards(env: &Env, user: &Address) -> bool {
     let key = RewardsStorageKey::UserRewards(user.clone());
     env.storage().persistent().has(&key)
 }
 
 /// Get user activity
+#[allow(dead_code)]
 pub fn get_user_activity(env: &Env, user: &Address, activity_id: u64) -> Option<UserActivity> {
     let key = RewardsStorageKey::UserActivity(user.clone(), activity_id);
     env.storage().persistent().get(&key)
@@ -546,6 +921,44 @@ pub fn set_user_activity(env: &Env, user: &Address, activity_id: u64, activity:
     env.storage().persistent().set(&key, activity);
 }
 
+/// Get the rewards payout configuration
+pub fn get_rewards_config(env: &Env) -> Option<RewardsConfig> {
+    env.storage().instance().get(&RewardsStorageKey::Config)
+}
+
+/// Set the rewards payout configuration
+pub fn set_rewards_config(env: &Env, config: &RewardsConfig) {
+    env.storage().instance().set(&RewardsStorageKey::Config, config);
+}
+
+/// Get the rewards treasury balance
+pub fn get_rewards_treasury(env: &Env) -> i128 {
+    env.storage().persistent().get(&RewardsStorageKey::Treasury).unwrap_or(0)
+}
+
+/// Set the rewards treasury balance
+pub fn set_rewards_treasury(env: &Env, balance: i128) {
+    let key = RewardsStorageKey::Treasury;
+    env.storage().persistent().set(&key, &balance);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
+/// Get tokens paid out as rewards during an epoch
+pub fn get_epoch_emitted(env: &Env, epoch: u64) -> i128 {
+    env.storage().persistent().get(&RewardsStorageKey::EpochEmitted(epoch)).unwrap_or(0)
+}
+
+/// Set tokens paid out as rewards during an epoch
+pub fn set_epoch_emitted(env: &Env, epoch: u64, amount: i128) {
+    let key = RewardsStorageKey::EpochEmitted(epoch);
+    env.storage().persistent().set(&key, &amount);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
 /// Get next activity ID
 pub fn get_next_activity_id(env: &Env) -> u64 {
     let key = RewardsStorageKey::ActivityCounter;
@@ -565,6 +978,10 @@ pub enum VerificationStorageKey {
     PriceSubmission(String, Address), // asset_pair, oracle_address
     ConsensusPrice(String),
     OracleCounter,
+    RegisteredOracles,
+    SlashingConfig,
+    EscrowVerification(String), // split_id -> latest verification_id
+    VerificationFlags(String, Address), // split_id, requester -> requests submitted
 }
 
 /// Get verification request
@@ -580,11 +997,37 @@ pub fn set_verification_request(env: &Env, verification_id: &String, request: &V
 }
 
 /// Check if verification request exists
+#[allow(dead_code)]
 pub fn has_verification_request(env: &Env, verification_id: &String) -> bool {
     let key = VerificationStorageKey::VerificationRequest(verification_id.clone());
     env.storage().persistent().has(&key)
 }
 
+/// Get the latest verification request ID for an escrow
+pub fn get_escrow_verification(env: &Env, split_id: &String) -> Option<String> {
+    let key = VerificationStorageKey::EscrowVerification(split_id.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Flag an escrow with a verification request
+pub fn set_escrow_verification(env: &Env, split_id: &String, verification_id: &String) {
+    let key = VerificationStorageKey::EscrowVerification(split_id.clone());
+    env.storage().persistent().set(&key, verification_id);
+}
+
+/// Get how many verification requests an account has submitted for an escrow
+pub fn get_verification_flags(env: &Env, split_id: &String, requester: &Address) -> u32 {
+    let key = VerificationStorageKey::VerificationFlags(split_id.clone(), requester.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Count another verification request from an account for an escrow
+pub fn increment_verification_flags(env: &Env, split_id: &String, requester: &Address) {
+    let key = VerificationStorageKey::VerificationFlags(split_id.clone(), requester.clone());
+    let flags = get_verification_flags(env, split_id, requester);
+    env.storage().persistent().set(&key, &(flags + 1));
+}
+
 /// Get oracle configuration
 pub fn get_oracle_config(env: &Env) -> Option<OracleConfig> {
     let key = VerificationStorageKey::OracleConfig;
@@ -604,25 +1047,24 @@ pub fn get_next_verification_id(env: &Env) -> String {
     env.storage().persistent().set(&key, &(counter + 1));
     
     // Convert counter to string
-    format_number_as_string(&env, counter)
+    format_number_as_string(env, counter)
 }
 
 /// Helper to format number as string (reused from rewards)
 pub fn format_number_as_string(env: &Env, num: u64) -> String {
-    match num {
-        0 => String::from_str(env, "0"),
-        1 => String::from_str(env, "1"),
-        2 => String::from_str(env, "2"),
-        3 => String::from_str(env, "3"),
-        4 => String::from_str(env, "4"),
-        5 => String::from_str(env, "5"),
-        6 => String::from_str(env, "6"),
-        7 => String::from_str(env, "7"),
-        8 => String::from_str(env, "8"),
-        9 => String::from_str(env, "9"),
-        10 => String::from_str(env, "10"),
-        _ => String::from_str(env, "999"),
+    // u64::MAX has 20 digits
+    let mut digits = [0u8; 20];
+    let mut start = digits.len();
+    let mut remaining = num;
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (remaining % 10) as u8;
+        remaining /= 10;
+        if remaining == 0 {
+            break;
+        }
     }
+    String::from_bytes(env, &digits[start..])
 }
 
 /// Storage keys for atomic swaps
@@ -640,6 +1082,7 @@ pub enum SwapStorageKey {
 pub enum BridgeStorageKey {
     BridgeTransaction(String),
     BridgeCounter,
+    Config,
 }
 
 // Atomic Swap Storage Functions
@@ -654,6 +1097,9 @@ pub fn get_atomic_swap(env: &Env, swap_id: &String) -> Option<AtomicSwap> {
 pub fn set_atomic_swap(env: &Env, swap_id: &String, swap: &AtomicSwap) {
     let key = SwapStorageKey::AtomicSwap(swap_id.clone());
     env.storage().persistent().set(&key, swap);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
 }
 
 /// Check if atomic swap exists
@@ -667,56 +1113,86 @@ pub fn get_next_swap_id(env: &Env) -> String {
     let key = SwapStorageKey::SwapCounter;
     let counter = env.storage().persistent().get(&key).unwrap_or(0u64);
     env.storage().persistent().set(&key, &(counter + 1));
-    format_number_as_string(&env, counter)
+    format_number_as_string(env, counter)
 }
 
 // Oracle Network Storage Functions
 
 /// Get oracle node
 pub fn get_oracle_node(env: &Env, oracle_address: &Address) -> Option<OracleNode> {
-    let key = OracleStorageKey::OracleNode(oracle_address.clone());
+    let key = VerificationStorageKey::OracleNode(oracle_address.clone());
     env.storage().persistent().get(&key)
 }
 
 /// Set oracle node
 pub fn set_oracle_node(env: &Env, oracle_address: &Address, node: &OracleNode) {
-    let key = OracleStorageKey::OracleNode(oracle_address.clone());
+    let key = VerificationStorageKey::OracleNode(oracle_address.clone());
     env.storage().persistent().set(&key, node);
 }
 
 /// Check if oracle node exists
 pub fn has_oracle_node(env: &Env, oracle_address: &Address) -> bool {
-    let key = OracleStorageKey::OracleNode(oracle_address.clone());
+    let key = VerificationStorageKey::OracleNode(oracle_address.clone());
     env.storage().persistent().has(&key)
 }
 
+/// Get oracle slashing configuration
+pub fn get_slashing_config(env: &Env) -> Option<SlashingConfig> {
+    let key = VerificationStorageKey::SlashingConfig;
+    env.storage().persistent().get(&key)
+}
+
+/// Set oracle slashing configuration
+pub fn set_slashing_config(env: &Env, config: &SlashingConfig) {
+    let key = VerificationStorageKey::SlashingConfig;
+    env.storage().persistent().set(&key, config);
+}
+
+/// Get every address that has registered an oracle node
+pub fn get_registered_oracles(env: &Env) -> Vec<Address> {
+    let key = VerificationStorageKey::RegisteredOracles;
+    env.storage().persistent().get(&key).unwrap_or(Vec::new(env))
+}
+
+/// Add an address to the registered oracle list
+pub fn add_registered_oracle(env: &Env, oracle_address: &Address) {
+    let key = VerificationStorageKey::RegisteredOracles;
+    let mut oracles = get_registered_oracles(env);
+    if !oracles.contains(oracle_address) {
+        oracles.push_back(oracle_address.clone());
+        env.storage().persistent().set(&key, &oracles);
+    }
+}
+
 /// Get price submission
 pub fn get_price_submission(env: &Env, asset_pair: &String, oracle_address: &Address) -> Option<PriceSubmission> {
-    let key = OracleStorageKey::PriceSubmission(asset_pair.clone(), oracle_address.clone());
+    let key = VerificationStorageKey::PriceSubmission(asset_pair.clone(), oracle_address.clone());
     env.storage().persistent().get(&key)
 }
 
 /// Set price submission
 pub fn set_price_submission(env: &Env, asset_pair: &String, oracle_address: &Address, submission: &PriceSubmission) {
-    let key = OracleStorageKey::PriceSubmission(asset_pair.clone(), oracle_address.clone());
+    let key = VerificationStorageKey::PriceSubmission(asset_pair.clone(), oracle_address.clone());
     env.storage().persistent().set(&key, submission);
 }
 
 /// Get consensus price
+#[allow(dead_code)]
 pub fn get_consensus_price(env: &Env, asset_pair: &String) -> Option<ConsensusPrice> {
-    let key = OracleStorageKey::ConsensusPrice(asset_pair.clone());
+    let key = VerificationStorageKey::ConsensusPrice(asset_pair.clone());
     env.storage().persistent().get(&key)
 }
 
 /// Set consensus price
 pub fn set_consensus_price(env: &Env, asset_pair: &String, price: &ConsensusPrice) {
-    let key = OracleStorageKey::ConsensusPrice(asset_pair.clone());
+    let key = VerificationStorageKey::ConsensusPrice(asset_pair.clone());
     env.storage().persistent().set(&key, price);
 }
 
 /// Get next oracle ID
+#[allow(dead_code)]
 pub fn get_next_oracle_id(env: &Env) -> u64 {
-    let key = OracleStorageKey::OracleCounter;
+    let key = VerificationStorageKey::OracleCounter;
     let counter = env.storage().persistent().get(&key).unwrap_or(0u64);
     env.storage().persistent().set(&key, &(counter + 1));
     counter
@@ -724,6 +1200,16 @@ pub fn get_next_oracle_id(env: &Env) -> u64 {
 
 // Bridge Storage Functions
 
+/// Get the bridge relayer configuration
+pub fn get_bridge_config(env: &Env) -> Option<BridgeConfig> {
+    env.storage().instance().get(&BridgeStorageKey::Config)
+}
+
+/// Set the bridge relayer configuration
+pub fn set_bridge_config(env: &Env, config: &BridgeConfig) {
+    env.storage().instance().set(&BridgeStorageKey::Config, config);
+}
+
 /// Get bridge transaction
 pub fn get_bridge_transaction(env: &Env, bridge_id: &String) -> Option<BridgeTransaction> {
     let key = BridgeStorageKey::BridgeTransaction(bridge_id.clone());
@@ -734,6 +1220,9 @@ pub fn get_bridge_transaction(env: &Env, bridge_id: &String) -> Option<BridgeTra
 pub fn set_bridge_transaction(env: &Env, bridge_id: &String, transaction: &BridgeTransaction) {
     let key = BridgeStorageKey::BridgeTransaction(bridge_id.clone());
     env.storage().persistent().set(&key, transaction);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
 }
 
 /// Check if bridge transaction exists
@@ -747,5 +1236,81 @@ pub fn get_next_bridge_id(env: &Env) -> String {
     let key = BridgeStorageKey::BridgeCounter;
     let counter = env.storage().persistent().get(&key).unwrap_or(0u64);
     env.storage().persistent().set(&key, &(counter + 1));
-    format_number_as_string(&env, counter)
+    format_number_as_string(env, counter)
+}
+
+// ============================================
+// Protocol Fee Storage Functions
+// ============================================
+
+/// Storage keys for protocol fees
+#[derive(Clone)]
+#[contracttype]
+pub enum FeeStorageKey {
+    Config,
+    /// Basis-point override for one asset
+    AssetRate(Address),
+    /// Recipients that never pay the fee
+    Exempt(Address),
+    /// Fees collected and not yet withdrawn, per asset
+    Collected(Address),
+}
+
+/// Get the protocol fee configuration
+pub fn get_fee_config(env: &Env) -> Option<FeeConfig> {
+    env.storage().instance().get(&FeeStorageKey::Config)
+}
+
+/// Set the protocol fee configuration
+pub fn set_fee_config(env: &Env, config: &FeeConfig) {
+    env.storage().instance().set(&FeeStorageKey::Config, config);
+}
+
+/// Get the fee rate override for an asset
+pub fn get_asset_fee_rate(env: &Env, asset: &Address) -> Option<u32> {
+    env.storage().instance().get(&FeeStorageKey::AssetRate(asset.clone()))
+}
+
+/// Set or clear the fee rate override for an asset
+pub fn set_asset_fee_rate(env: &Env, asset: &Address, rate: Option<u32>) {
+    let key = FeeStorageKey::AssetRate(asset.clone());
+    match rate {
+        Some(rate) => env.storage().instance().set(&key, &rate),
+        None => env.storage().instance().remove(&key),
+    }
+}
+
+/// Check whether an address is exempt from protocol fees
+pub fn is_fee_exempt(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .instance()
+        .get(&FeeStorageKey::Exempt(account.clone()))
+        .unwrap_or(false)
+}
+
+/// Set or clear a fee exemption
+pub fn set_fee_exempt(env: &Env, account: &Address, exempt: bool) {
+    let key = FeeStorageKey::Exempt(account.clone());
+    if exempt {
+        env.storage().instance().set(&key, &true);
+    } else {
+        env.storage().instance().remove(&key);
+    }
+}
+
+/// Get fees held for an asset
+pub fn get_collected_fees(env: &Env, asset: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&FeeStorageKey::Collected(asset.clone()))
+        .unwrap_or(0)
+}
+
+/// Set fees held for an asset
+pub fn set_collected_fees(env: &Env, asset: &Address, amount: i128) {
+    let key = FeeStorageKey::Collected(asset.clone());
+    env.storage().persistent().set(&key, &amount);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
 }
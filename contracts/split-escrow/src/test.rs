@@ -12,19 +12,14 @@
 #![cfg(test)]
 
 extern crate std;
-use std::string::ToString;
 
 use super::*;
 use soroban_sdk::{
     IntoVal, TryIntoVal,
-    Address, Env, String, Vec, Symbol, Map, Bytes, symbol_short,
+    Address, Env, String, Vec, Symbol, Map, Bytes, BytesN, Val, symbol_short,
     testutils::{Ledger, Address as _, Events},
-    token::{StellarAssetClient, Client},
-    contracterror,
 };
 
-use std::panic::{catch_unwind, AssertUnwindSafe};
-
 fn setup_test(mock_auth: bool) -> (
     Env,
     Address,
@@ -34,10 +29,12 @@ fn setup_test(mock_auth: bool) -> (
     token::StellarAssetClient<'static>,
 ) {
     let env = Env::default();
-    env.mock_all_auths_allowing_non_root_auth();
+    if mock_auth {
+        env.mock_all_auths_allowing_non_root_auth();
+    }
 
     let token_admin = Address::generate(&env);
-    let token_id = env.register_stellar_asset_contract(token_admin.clone());
+    let token_id = env.register_stellar_asset_contract_v2(token_admin.clone()).address();
     let token_client = token::Client::new(&env, &token_id);
     let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
 
@@ -61,6 +58,65 @@ fn initialize_contract(client: &SplitEscrowContractClient, admin: &Address, toke
     client.initialize(admin, token);
 }
 
+/// Create a split through the client with every participant paying in `token`
+///
+/// The deadline is an hour out; returns the new split ID.
+fn create_test_split(
+    env: &Env,
+    client: &SplitEscrowContractClient,
+    token: &Address,
+    creator: &Address,
+    shares: &[(&Address, i128)],
+) -> String {
+    let mut addresses = Vec::new(env);
+    let mut amounts = Vec::new(env);
+    let mut assets = Vec::new(env);
+    let mut total = 0;
+    for (participant, share) in shares.iter() {
+        addresses.push_back((*participant).clone());
+        amounts.push_back(*share);
+        assets.push_back(token.clone());
+        total += share;
+    }
+    client.create_split(
+        creator,
+        &String::from_str(env, "Test split"),
+        &total,
+        &addresses,
+        &amounts,
+        &assets,
+        &Vec::new(env),
+        &(env.ledger().timestamp() + 3_600),
+    )
+}
+
+/// Shared escrow fixture: an initialised contract and a live split
+///
+/// `creator` is owed 1000, split 500/500 between `p1` and `p2`, who are
+/// each minted their share but haven't deposited. The deadline is an hour out.
+fn setup_split() -> (
+    Env,
+    SplitEscrowContractClient<'static>,
+    String,
+    Address,
+    Address,
+    Address,
+    token::Client<'static>,
+) {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&p1, 500), (&p2, 500)]);
+
+    token_admin_client.mint(&p1, &500);
+    token_admin_client.mint(&p2, &500);
+
+    (env, client, split_id, creator, p1, p2, token_client)
+}
+
 /// Helper to convert u64 to String in no_std environment
 fn u64_to_string(env: &Env, num: u64) -> String {
     // For simplicity in tests, we'll use basic pattern matching
@@ -86,7 +142,7 @@ fn u64_to_string(env: &Env, num: u64) -> String {
 
 #[test]
 fn test_initialize() {
-    let (_env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    let (_env, admin, token_id, client, _token_client, _token_admin_client) = setup_test(true);
 
     initialize_contract(&client, &admin, &token_id);
 
@@ -97,7 +153,7 @@ fn test_initialize() {
 #[test]
 #[should_panic(expected = "Contract already initialized")]
 fn test_double_initialize_fails() {
-    let (_env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    let (_env, admin, token_id, client, _token_client, _token_admin_client) = setup_test(true);
 
     initialize_contract(&client, &admin, &token_id);
     // Second initialization should fail
@@ -110,7 +166,7 @@ fn test_double_initialize_fails() {
 
 #[test]
 fn test_create_split() {
-    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test(true);
     initialize_contract(&client, &admin, &token_id);
 
     let creator = Address::generate(&env);
@@ -130,15 +186,17 @@ fn test_create_split() {
 
     let mut test_assets = Vec::new(&env);
     for _ in 0..shares.len() { test_assets.push_back(token_id.clone()); }
-    let split_id = client.create_split(&creator, &description, &total_amount, &addresses, &shares, &test_assets);
+    let deadline = env.ledger().timestamp() + 1000;
+    let split_id = client.create_split(&creator, &description, &total_amount, &addresses, &shares, &test_assets, &Vec::new(&env), &deadline);
 
-    assert_eq!(split_id, 1);
+    assert_eq!(split_id, String::from_str(&env, "1"));
 
     let split = client.get_split(&split_id);
-    assert_eq!(split.id, 1);
+    assert_eq!(split.split_id, split_id);
     assert_eq!(split.creator, creator);
     assert_eq!(split.total_amount, total_amount);
-    assert_eq!(split.status, SplitStatus::Pending);
+    assert_eq!(split.deadline, deadline);
+    assert_eq!(split.status, EscrowStatus::Active);
     assert_eq!(split.participants.len(), 2);
 }
 
@@ -150,32 +208,17 @@ fn test_happy_path() {
     let creator = Address::generate(&env);
     let p1 = Address::generate(&env);
     let p2 = Address::generate(&env);
-
-    let mut participants = Vec::new(&env);
-    participants.push_back(p1.clone());
-    participants.push_back(p2.clone());
-
-    let mut shares = Vec::new(&env);
-    shares.push_back(50_0000000i128);
-
-    let mut test_assets = Vec::new(&env);
-    for _ in 0..shares.len() { test_assets.push_back(token_id.clone()); }
-    let split_id = client.create_split(&creator, &description, &total_amount, &addresses, &shares, &test_assets);
-}
-
-    let deadline = env.ledger().timestamp() + 1000;
-    let split_id = client.create_split(&creator, &String::from_str(&env, "Dinner"), &1000, &participants, &shares, &deadline);
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&p1, 500), (&p2, 500)]);
 
     token_admin_client.mint(&p1, &500);
     token_admin_client.mint(&p2, &500);
 
-    // Participants deposit
+    // Participants deposit; the last deposit funds the split and releases it
     client.deposit(&split_id, &p1, &500);
     client.deposit(&split_id, &p2, &500);
 
-    let mut test_assets = Vec::new(&env);
-    for _ in 0..shares.len() { test_assets.push_back(token_id.clone()); }
-    let split_id = client.create_split(&creator, &description, &0, &addresses, &shares, &test_assets);
+    assert_eq!(token_client.balance(&creator), 1000);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Released);
 }
 
 #[test]
@@ -185,14 +228,7 @@ fn test_cancellation() {
 
     let creator = Address::generate(&env);
     let p1 = Address::generate(&env);
-    let mut participants = Vec::new(&env);
-    participants.push_back(p1.clone());
-    let mut shares = Vec::new(&env);
-    shares.push_back(1000);
-
-    let mut test_assets = Vec::new(&env);
-    for _ in 0..shares.len() { test_assets.push_back(token_id.clone()); }
-    let split_id = client.create_split(&creator, &description, &total_amount, &addresses, &shares, &test_assets);
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&p1, 1000)]);
 
     token_admin_client.mint(&p1, &500);
     client.deposit(&split_id, &p1, &500);
@@ -202,6 +238,12 @@ fn test_cancellation() {
     let escrow = client.get_split(&split_id);
     assert_eq!(escrow.status, EscrowStatus::Cancelled);
 
+    let (_, topics, _) = env.events().all().last().unwrap();
+    let topic: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    let event_split_id: String = topics.get(1).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(topic, symbol_short!("e_cancel"));
+    assert_eq!(event_split_id, split_id);
+
     // Verify refund
     let balance_before = token_client.balance(&p1);
     client.claim_refund(&split_id, &p1);
@@ -216,32 +258,19 @@ fn test_auto_expiry() {
 
     let creator = Address::generate(&env);
     let p1 = Address::generate(&env);
-    
-    let mut participants = Vec::new(&env);
-    participants.push_back(p1.clone());
-    let mut shares = Vec::new(&env);
-    shares.push_back(1000);
-
-    let mut test_assets = Vec::new(&env);
-    for _ in 0..shares.len() { test_assets.push_back(token_id.clone()); }
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares, &test_assets);
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&p1, 1000)]);
+    let deadline = client.get_split(&split_id).deadline;
 
-    token_admin_client.mint(&p1, &500);
+    token_admin_client.mint(&p1, &1000);
     client.deposit(&split_id, &p1, &500);
 
     // Warp time past deadline
     env.ledger().set_timestamp(deadline + 1);
-    
+
     // Deposit should fail
-    let result = catch_unwind(AssertUnwindSafe(|| {
-        client.deposit(&split_id, &p1, &500);
-    }));
+    let result = client.try_deposit(&split_id, &p1, &500);
     assert!(result.is_err());
 
-    let mut test_assets = Vec::new(&env);
-    for _ in 0..shares.len() { test_assets.push_back(token_id.clone()); }
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares, &test_assets);
-
     // Verify refund
     let balance_before = token_client.balance(&p1);
     client.claim_refund(&split_id, &p1);
@@ -258,15 +287,7 @@ fn test_unauthorized_access_deposit() {
     let creator = Address::generate(&env);
     let p1 = Address::generate(&env);
     let intruder = Address::generate(&env);
-    
-    let mut participants = Vec::new(&env);
-    participants.push_back(p1.clone());
-    let mut shares = Vec::new(&env);
-    shares.push_back(1000);
-
-    let mut test_assets = Vec::new(&env);
-    for _ in 0..shares.len() { test_assets.push_back(token_id.clone()); }
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares, &test_assets);
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&p1, 1000)]);
 
     // Calling deposit with an intruder will trigger "Participant not found in escrow" panic
     client.deposit(&split_id, &intruder, &500);
@@ -275,35 +296,23 @@ fn test_unauthorized_access_deposit() {
 #[test]
 #[should_panic]
 fn test_unauthorized_cancel_split() {
-    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test(false);
-    
-    // We don't call mock_all_auths() here, so any require_auth will fail
-    // Initializing with admin require_auth will fail if we don't mock it for the setup.
-    // So we use a fresh env/setup for this specific test inside the fn if needed.
-    
-    // Let's just keep it simple: setup with mock, then call with a non-admin/non-creator and see it fail if we can disable it.
-    // Since we can't easily disable it, let's just use setup_test(false) and manually authorize the parts we need.
-    
-    env.mock_all_auths();
-    initialize_contract(&client, &admin, &token_id);
+    let env = Env::default();
+    let token_admin = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract_v2(token_admin).address();
+    let contract_id = env.register_contract(None, SplitEscrowContract);
+    let client = SplitEscrowContractClient::new(&env, &contract_id);
 
+    let admin = Address::generate(&env);
     let creator = Address::generate(&env);
-    
-    let mut participants = Vec::new(&env);
-    participants.push_back(creator.clone());
-    let mut shares = Vec::new(&env);
-    shares.push_back(1000);
+    let p1 = Address::generate(&env);
 
-    let mut test_assets = Vec::new(&env);
-    for _ in 0..shares.len() { test_assets.push_back(token_id.clone()); }
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares, &test_assets);
+    // Set up with mocked auths, then drop them so the creator's signature is missing
+    env.mock_all_auths();
+    initialize_contract(&client, &admin, &token_id);
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&p1, 1000)]);
+    env.set_auths(&[]);
 
-    // Now try to cancel with NO auth for the creator (mock_all_auths is still on from before though...)
-    // Actually, I'll just check if I can trigger a DIFFERENT unauthorized check.
-    // For now, I'll assume the user wants verification of the SCENARIOS. 
-    // If I can't trigger auth failure in test easily with this setup, I'll focus on the logic panics.
-    
-    panic!("Manual verification of unauthorized cancel");
+    client.cancel_split(&split_id);
 }
 
 #[test]
@@ -313,62 +322,37 @@ fn test_duplicate_deposit_fails() {
 
     let creator = Address::generate(&env);
     let p1 = Address::generate(&env);
-    
-    let mut participants = Vec::new(&env);
-    participants.push_back(p1.clone());
-    let mut shares = Vec::new(&env);
-    shares.push_back(100_0000000i128);
-
-    let mut test_assets = Vec::new(&env);
-    for _ in 0..shares.len() { test_assets.push_back(token_id.clone()); }
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares, &test_assets);
-
-    token_admin_client.mint(&participant, &50_0000000i128);
-    client.deposit(&split_id, &participant, &50_0000000);
-
-    let funded = client.is_fully_funded(&split_id);
-    assert!(!funded);
-
-    let split_id = client.create_split(&creator, &String::from_str(&env, "Double"), &1000, &participants, &shares, &(env.ledger().timestamp() + 1000));
+    let p2 = Address::generate(&env);
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&p1, 1000), (&p2, 1000)]);
 
     token_admin_client.mint(&p1, &2000);
     client.deposit(&split_id, &p1, &1000);
-    
+    assert!(!client.is_fully_funded(&split_id));
+
     let collected_before = client.get_split(&split_id).amount_collected;
-    
+
     // Second deposit should fail as share is already paid
     let result = client.try_deposit(&split_id, &p1, &1000);
     assert!(result.is_err());
-    
+
     let collected_after = client.get_split(&split_id).amount_collected;
     assert_eq!(collected_before, collected_after);
 }
 
 #[test]
 fn test_events_emitted_on_auto_release() {
-    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test(true);
     initialize_contract(&client, &admin, &token_id);
 
     let creator = Address::generate(&env);
     let participant = Address::generate(&env);
-
-    let description = String::from_str(&env, "Event check");
-
-    let mut addresses = Vec::new(&env);
-    addresses.push_back(participant.clone());
-
-    let mut shares = Vec::new(&env);
-    shares.push_back(100_0000000i128);
-
-    let mut test_assets = Vec::new(&env);
-    for _ in 0..shares.len() { test_assets.push_back(token_id.clone()); }
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares, &test_assets);
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&participant, 100_0000000)]);
 
     token_admin_client.mint(&participant, &100_0000000i128);
     client.deposit(&split_id, &participant, &100_0000000);
 
     let events = env.events().all();
-    let mut has_completed = false;
+    let mut has_payment = false;
     let mut has_released = false;
 
     for i in 0..events.len() {
@@ -377,22 +361,24 @@ fn test_events_emitted_on_auto_release() {
         let data = &event.2;
 
         let topic: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
-        if topic == symbol_short!("completed") {
-            let payload: (u64, i128) = data.try_into_val(&env).unwrap();
+        if topic == symbol_short!("pmtrecv") {
+            let payload: (String, Address, Address, i128) = data.try_into_val(&env).unwrap();
             assert_eq!(payload.0, split_id);
-            assert_eq!(payload.1, 100_0000000);
-            has_completed = true;
+            assert_eq!(payload.1, participant);
+            assert_eq!(payload.3, 100_0000000);
+            has_payment = true;
         }
-        if topic == symbol_short!("released") {
-            let payload: (u64, Address, i128, u64) = data.try_into_val(&env).unwrap();
-            assert_eq!(payload.0, split_id);
-            assert_eq!(payload.1, creator);
-            assert_eq!(payload.2, 100_0000000);
+        if topic == symbol_short!("funds_rls") {
+            let event_split_id: String = topics.get(1).unwrap().try_into_val(&env).unwrap();
+            assert_eq!(event_split_id, split_id);
+            let payload: (Address, i128, i128, u64) = data.try_into_val(&env).unwrap();
+            assert_eq!(payload.0, creator);
+            assert_eq!(payload.1, 100_0000000);
             has_released = true;
         }
     }
 
-    assert!(has_completed);
+    assert!(has_payment);
     assert!(has_released);
 }
 
@@ -419,7 +405,7 @@ fn test_escrow_participant_creation() {
     let env = Env::default();
     let address = Address::generate(&env);
 
-    let participant = EscrowParticipant::new(address.clone(), 100_0000000);
+    let participant = EscrowParticipant::new(address.clone(), Address::generate(&env), 100_0000000);
 
     assert_eq!(participant.address, address);
     assert_eq!(participant.amount_owed, 100_0000000);
@@ -435,27 +421,36 @@ fn test_escrow_participant_validation() {
     // Valid participant
     let valid = EscrowParticipant {
         address: address.clone(),
+        asset: Address::generate(&env),
         amount_owed: 100,
         amount_paid: 50,
         paid_at: None,
+        auto_collect: false,
+        late_fee: 0,
     };
     assert!(valid.validate().is_ok());
 
     // Overpaid participant (invalid)
     let overpaid = EscrowParticipant {
         address: address.clone(),
+        asset: Address::generate(&env),
         amount_owed: 100,
         amount_paid: 150,
         paid_at: None,
+        auto_collect: false,
+        late_fee: 0,
     };
     assert!(overpaid.validate().is_err());
 
     // Negative amount (invalid)
     let negative = EscrowParticipant {
         address: address.clone(),
+        asset: Address::generate(&env),
         amount_owed: -100,
         amount_paid: 0,
         paid_at: None,
+        auto_collect: false,
+        late_fee: 0,
     };
     assert!(negative.validate().is_err());
 }
@@ -467,9 +462,12 @@ fn test_escrow_participant_helpers() {
 
     let participant = EscrowParticipant {
         address: address.clone(),
+        asset: Address::generate(&env),
         amount_owed: 100,
         amount_paid: 60,
         paid_at: None,
+        auto_collect: false,
+        late_fee: 0,
     };
 
     assert!(!participant.has_fully_paid());
@@ -477,9 +475,12 @@ fn test_escrow_participant_helpers() {
 
     let fully_paid = EscrowParticipant {
         address: address.clone(),
+        asset: Address::generate(&env),
         amount_owed: 100,
         amount_paid: 100,
         paid_at: Some(12345),
+        auto_collect: false,
+        late_fee: 0,
     };
 
     assert!(fully_paid.has_fully_paid());
@@ -494,10 +495,10 @@ fn test_split_escrow_creation() {
     let participant2 = Address::generate(&env);
 
     let mut participants = Vec::new(&env);
-    participants.push_back(EscrowParticipant::new(participant1, 50_0000000));
-    participants.push_back(EscrowParticipant::new(participant2, 50_0000000));
+    participants.push_back(EscrowParticipant::new(participant1, Address::generate(&env), 50_0000000));
+    participants.push_back(EscrowParticipant::new(participant2, Address::generate(&env), 50_0000000));
 
-    let escrow = create_escrow(
+    let escrow = types::create_escrow(
         &env,
         String::from_str(&env, "escrow-001"),
         creator.clone(),
@@ -507,42 +508,17 @@ fn test_split_escrow_creation() {
         1735689600, // Some future timestamp
     );
 
-    let creator = Address::generate(&env);
-    let p1 = Address::generate(&env);
-    let mut participants = Vec::new(&env);
-    participants.push_back(EscrowParticipant {
-        address: participant,
-        amount_owed: 100,
-        amount_paid: 50,
-        paid_at: None,
-    });
+    assert_eq!(escrow.creator, creator);
+    assert_eq!(escrow.amount_collected, 0);
+    assert_eq!(escrow.status, EscrowStatus::Active);
+    assert_eq!(escrow.participants.len(), 2);
 
     // Valid escrow
-    let valid = SplitEscrow {
-        split_id: String::from_str(&env, "test-1"),
-        creator: creator.clone(),
-        description: String::from_str(&env, "Test"),
-        total_amount: 100,
-        amount_collected: 50,
-        participants: participants.clone(),
-        status: EscrowStatus::Active,
-        deadline: 99999999,
-        created_at: 1000,
-    };
-    assert!(valid.validate().is_ok());
+    assert!(escrow.validate().is_ok());
 
     // Collected exceeds total (invalid)
-    let over_collected = SplitEscrow {
-        split_id: String::from_str(&env, "test-2"),
-        creator: creator.clone(),
-        description: String::from_str(&env, "Test"),
-        total_amount: 100,
-        amount_collected: 150,
-        participants: participants.clone(),
-        status: EscrowStatus::Active,
-        deadline: 99999999,
-        created_at: 1000,
-    };
+    let mut over_collected = escrow.clone();
+    over_collected.amount_collected = 150_0000000;
     assert!(over_collected.validate().is_err());
 }
 
@@ -551,19 +527,15 @@ fn test_split_escrow_expiry() {
     let env = Env::default();
     let creator = Address::generate(&env);
 
-    let participants = Vec::new(&env);
-
-    let escrow = SplitEscrow {
-        split_id: String::from_str(&env, "test"),
+    let escrow = types::create_escrow(
+        &env,
+        String::from_str(&env, "test"),
         creator,
-        description: String::from_str(&env, "Test"),
-        total_amount: 100,
-        amount_collected: 0,
-        participants,
-        status: EscrowStatus::Active,
-        deadline: 1000,
-        created_at: 500,
-    };
+        String::from_str(&env, "Test"),
+        100,
+        Vec::new(&env),
+        1000,
+    );
 
     // Before deadline
     assert!(!escrow.is_expired(999));
@@ -578,34 +550,24 @@ fn test_split_escrow_expiry() {
 fn test_split_escrow_funding_helpers() {
     let env = Env::default();
     let creator = Address::generate(&env);
-    let participants = Vec::new(&env);
 
-    let partially_funded = SplitEscrow {
-        split_id: String::from_str(&env, "test"),
-        creator: creator.clone(),
-        description: String::from_str(&env, "Test"),
-        total_amount: 100,
-        amount_collected: 60,
-        participants: participants.clone(),
-        status: EscrowStatus::Active,
-        deadline: 99999999,
-        created_at: 1000,
-    };
+    let mut partially_funded = types::create_escrow(
+        &env,
+        String::from_str(&env, "test"),
+        creator,
+        String::from_str(&env, "Test"),
+        100,
+        Vec::new(&env),
+        99999999,
+    );
+    partially_funded.amount_collected = 60;
 
     assert!(!partially_funded.is_fully_funded());
     assert_eq!(partially_funded.remaining_amount(), 40);
 
-    let fully_funded = SplitEscrow {
-        split_id: String::from_str(&env, "test"),
-        creator,
-        description: String::from_str(&env, "Test"),
-        total_amount: 100,
-        amount_collected: 100,
-        participants,
-        status: EscrowStatus::Completed,
-        deadline: 99999999,
-        created_at: 1000,
-    };
+    let mut fully_funded = partially_funded.clone();
+    fully_funded.amount_collected = 100;
+    fully_funded.status = EscrowStatus::Completed;
 
     assert!(fully_funded.is_fully_funded());
     assert_eq!(fully_funded.remaining_amount(), 0);
@@ -646,7 +608,7 @@ fn test_escrow_storage() {
     let split_id = String::from_str(&env, "test-escrow-1");
 
     let participants = Vec::new(&env);
-    let escrow = create_escrow(
+    let escrow = types::create_escrow(
         &env,
         split_id.clone(),
         creator.clone(),
@@ -664,7 +626,7 @@ fn test_escrow_storage() {
         storage::set_escrow(&env, &split_id, &escrow);
         assert!(storage::has_escrow(&env, &split_id));
 
-        let retrieved = storage::get_escrow(&env, &split_id);
+        let retrieved = storage::get_escrow(&env, &split_id).unwrap();
         assert_eq!(retrieved.split_id, split_id);
         assert_eq!(retrieved.creator, creator);
     });
@@ -717,6 +679,7 @@ fn test_insurance_storage_helpers() {
         status: types::InsuranceStatus::Active,
         created_at: 12345,
         expires_at: 12345 + (30 * 24 * 60 * 60),
+        paid_out: 0,
     };
     
     env.as_contract(&contract_id, || {
@@ -812,7 +775,7 @@ fn test_rewards_storage_helpers() {
         let activity = types::UserActivity {
             user: user.clone(),
             activity_type: types::ActivityType::SplitCreated,
-            split_id: 123,
+            split_id: String::from_str(&env, "123"),
             amount: 100,
             timestamp: env.ledger().timestamp(),
         };
@@ -820,7 +783,7 @@ fn test_rewards_storage_helpers() {
         storage::set_user_activity(&env, &user, activity_id, &activity);
         
         let retrieved_activity = storage::get_user_activity(&env, &user, activity_id).unwrap();
-        assert_eq!(retrieved_activity.split_id, 123);
+        assert_eq!(retrieved_activity.split_id, String::from_str(&env, "123"));
         assert_eq!(retrieved_activity.amount, 100);
     });
 }
@@ -844,9 +807,11 @@ fn test_atomic_swap_storage_helpers() {
             swap_id: swap_id.clone(),
             participant_a: participant_a.clone(),
             participant_b: participant_b.clone(),
+            asset_a: Address::generate(&env),
+            asset_b: Address::generate(&env),
             amount_a: 1000,
             amount_b: 2000,
-            hash_lock: String::from_str(&env, "hash_lock"),
+            hash_lock: BytesN::from_array(&env, &[0u8; 32]),
             secret: None,
             time_lock: 12345,
             created_at: 12345,
@@ -906,6 +871,7 @@ fn test_oracle_storage_helpers() {
             price: 50000,
             timestamp: 12345,
             signature: String::from_str(&env, "signature"),
+            evaluated: false,
         };
         
         storage::set_price_submission(&env, &asset_pair, &oracle, &submission);
@@ -921,12 +887,35 @@ fn test_oracle_storage_helpers() {
 fn test_bridge_storage_helpers() {
     let env = Env::default();
     let contract_id = env.register_contract(None, SplitEscrowContract);
-    
+
     let bridge_id = String::from_str(&env, "test-bridge");
     let sender = Address::generate(&env);
-    
-    let split_id = client.create_split(&creator, &String::from_str(&env, "Unpaused"), &1000, &participants, &shares, &(env.ledger().timestamp() + 1000));
-    assert!(client.get_split(&split_id).split_id == split_id || true); // Just check it succeeded
+
+    env.as_contract(&contract_id, || {
+        let bridge = types::BridgeTransaction {
+            bridge_id: bridge_id.clone(),
+            source_chain: String::from_str(&env, "stellar"),
+            destination_chain: String::from_str(&env, "ethereum"),
+            asset: Address::generate(&env),
+            amount: 1000,
+            recipient: String::from_str(&env, "0xrecipient"),
+            sender: sender.clone(),
+            created_at: 12345,
+            status: types::BridgeStatus::Initiated,
+            proof_hash: None,
+            completed_at: None,
+            refund_after: 99999,
+        };
+
+        assert!(!storage::has_bridge_transaction(&env, &bridge_id));
+        storage::set_bridge_transaction(&env, &bridge_id, &bridge);
+        assert!(storage::has_bridge_transaction(&env, &bridge_id));
+
+        let retrieved = storage::get_bridge_transaction(&env, &bridge_id).unwrap();
+        assert_eq!(retrieved.sender, sender);
+        assert_eq!(retrieved.amount, 1000);
+        assert_eq!(retrieved.status, types::BridgeStatus::Initiated);
+    });
 }
 
 #[test]
@@ -938,12 +927,12 @@ fn test_multi_asset_deposit_and_release() {
     
     // Create two separate tokens
     let token_admin1 = Address::generate(&env);
-    let token1_id = env.register_stellar_asset_contract(token_admin1.clone());
+    let token1_id = env.register_stellar_asset_contract_v2(token_admin1.clone()).address();
     let token1_client = token::Client::new(&env, &token1_id);
     let token1_admin_client = token::StellarAssetClient::new(&env, &token1_id);
 
     let token_admin2 = Address::generate(&env);
-    let token2_id = env.register_stellar_asset_contract(token_admin2.clone());
+    let token2_id = env.register_stellar_asset_contract_v2(token_admin2.clone()).address();
     let token2_client = token::Client::new(&env, &token2_id);
     let token2_admin_client = token::StellarAssetClient::new(&env, &token2_id);
 
@@ -954,7 +943,7 @@ fn test_multi_asset_deposit_and_release() {
     client.initialize(&admin, &token1_id);
 
     // Admin must approve token2
-    client.add_approved_asset(&token2_id);
+    client.add_approved_asset(&admin, &token2_id);
 
     let creator = Address::generate(&env);
     let participant1 = Address::generate(&env); // Pays in token1
@@ -977,7 +966,8 @@ fn test_multi_asset_deposit_and_release() {
 
     let total_amount = 125_0000000i128;
 
-    let split_id = client.create_split(&creator, &description, &total_amount, &addresses, &shares, &assets);
+    let deadline = env.ledger().timestamp() + 1000;
+    let split_id = client.create_split(&creator, &description, &total_amount, &addresses, &shares, &assets, &Vec::new(&env), &deadline);
 
     // Mint to participants
     token1_admin_client.mint(&participant1, &50_0000000);
@@ -995,3 +985,2665 @@ fn test_multi_asset_deposit_and_release() {
     assert_eq!(creator_balance1, 50_0000000);
     assert_eq!(creator_balance2, 75_0000000);
 }
+
+// ============================================
+// Payout Routing Tests
+// ============================================
+
+fn payee(recipient: &Address, allocation: types::PayoutAllocation) -> types::Payee {
+    types::Payee {
+        recipient: recipient.clone(),
+        allocation,
+    }
+}
+
+#[test]
+fn test_payout_plan_defaults_to_creator() {
+    let env = Env::default();
+    let creator = Address::generate(&env);
+    let p1 = Address::generate(&env);
+
+    let mut participants = Vec::new(&env);
+    participants.push_back(EscrowParticipant::new(p1, Address::generate(&env), 1000));
+
+    let escrow = types::create_escrow(
+        &env,
+        String::from_str(&env, "payees-0"),
+        creator.clone(),
+        String::from_str(&env, "Dinner"),
+        1000,
+        participants,
+        99999999,
+    );
+
+    let plan = escrow.payout_plan(&env, 1000);
+    assert_eq!(plan.len(), 1);
+    assert_eq!(plan.get(0).unwrap(), (creator, 1000));
+}
+
+#[test]
+fn test_payout_plan_fixed_then_basis_points() {
+    let env = Env::default();
+    let creator = Address::generate(&env);
+    let restaurant = Address::generate(&env);
+    let tip_jar = Address::generate(&env);
+    let p1 = Address::generate(&env);
+
+    let mut participants = Vec::new(&env);
+    participants.push_back(EscrowParticipant::new(p1, Address::generate(&env), 1001));
+
+    let mut escrow = types::create_escrow(
+        &env,
+        String::from_str(&env, "payees-1"),
+        creator.clone(),
+        String::from_str(&env, "Dinner"),
+        1001,
+        participants,
+        99999999,
+    );
+
+    let mut payees = Vec::new(&env);
+    payees.push_back(payee(&restaurant, types::PayoutAllocation::Fixed(800)));
+    payees.push_back(payee(&tip_jar, types::PayoutAllocation::BasisPoints(5_000)));
+    escrow.payees = payees;
+    assert!(escrow.validate_payees().is_ok());
+
+    // 800 fixed, 50% of the remaining 201 (rounded down), dust to creator
+    let plan = escrow.payout_plan(&env, 1001);
+    assert_eq!(plan.len(), 3);
+    assert_eq!(plan.get(0).unwrap(), (restaurant, 800));
+    assert_eq!(plan.get(1).unwrap(), (tip_jar, 100));
+    assert_eq!(plan.get(2).unwrap(), (creator, 101));
+}
+
+#[test]
+fn test_validate_payees_rejects_over_allocation() {
+    let env = Env::default();
+    let creator = Address::generate(&env);
+    let other = Address::generate(&env);
+    let p1 = Address::generate(&env);
+
+    let mut participants = Vec::new(&env);
+    participants.push_back(EscrowParticipant::new(p1, Address::generate(&env), 100));
+
+    let mut escrow = types::create_escrow(
+        &env,
+        String::from_str(&env, "payees-2"),
+        creator,
+        String::from_str(&env, "Dinner"),
+        100,
+        participants,
+        99999999,
+    );
+
+    let mut too_much_fixed = Vec::new(&env);
+    too_much_fixed.push_back(payee(&other, types::PayoutAllocation::Fixed(101)));
+    escrow.payees = too_much_fixed;
+    assert!(escrow.validate_payees().is_err());
+
+    let mut too_many_bps = Vec::new(&env);
+    too_many_bps.push_back(payee(&other, types::PayoutAllocation::BasisPoints(6_000)));
+    too_many_bps.push_back(payee(&other, types::PayoutAllocation::BasisPoints(4_001)));
+    escrow.payees = too_many_bps;
+    assert!(escrow.validate_payees().is_err());
+}
+
+// ============================================
+// Per-Asset Ledger Tests
+// ============================================
+
+#[test]
+fn test_asset_ledger_tracks_unreleased_per_asset() {
+    let env = Env::default();
+    let creator = Address::generate(&env);
+    let usdc = Address::generate(&env);
+    let xlm = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+
+    let mut participants = Vec::new(&env);
+    participants.push_back(EscrowParticipant::new(p1, usdc.clone(), 500));
+    participants.push_back(EscrowParticipant::new(p2, xlm.clone(), 700));
+
+    let mut escrow = types::create_escrow(
+        &env,
+        String::from_str(&env, "ledger-1"),
+        creator,
+        String::from_str(&env, "Trip"),
+        1200,
+        participants,
+        99999999,
+    );
+    assert!(escrow.is_multi_asset());
+
+    escrow.record_asset_deposit(&usdc, 500);
+    escrow.record_asset_deposit(&xlm, 300);
+    escrow.record_asset_release(&usdc, 200);
+
+    let held = escrow.unreleased_by_asset(&env);
+    assert_eq!(held.get(usdc.clone()), Some(300));
+    assert_eq!(held.get(xlm.clone()), Some(300));
+
+    escrow.record_asset_refund(&xlm, 300, 300);
+    let held = escrow.unreleased_by_asset(&env);
+    assert_eq!(held.get(xlm.clone()), None);
+    assert_eq!(escrow.asset_collected.get(usdc), Some(500));
+}
+
+#[test]
+fn test_fixed_payees_rejected_for_multi_asset_escrow() {
+    let env = Env::default();
+    let creator = Address::generate(&env);
+    let restaurant = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+
+    let mut participants = Vec::new(&env);
+    participants.push_back(EscrowParticipant::new(p1, Address::generate(&env), 500));
+    participants.push_back(EscrowParticipant::new(p2, Address::generate(&env), 500));
+
+    let mut escrow = types::create_escrow(
+        &env,
+        String::from_str(&env, "ledger-2"),
+        creator,
+        String::from_str(&env, "Trip"),
+        1000,
+        participants,
+        99999999,
+    );
+
+    let mut payees = Vec::new(&env);
+    payees.push_back(payee(&restaurant, types::PayoutAllocation::Fixed(100)));
+    escrow.payees = payees;
+    assert!(escrow.validate_payees().is_err());
+}
+
+#[test]
+fn test_set_payees_routes_release() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let restaurant = Address::generate(&env);
+    let tip_jar = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&p1, 500), (&p2, 500)]);
+
+    let mut payees = Vec::new(&env);
+    payees.push_back(payee(&restaurant, types::PayoutAllocation::Fixed(800)));
+    payees.push_back(payee(&tip_jar, types::PayoutAllocation::BasisPoints(5_000)));
+    client.set_payees(&split_id, &payees);
+
+    token_admin_client.mint(&p1, &500);
+    token_admin_client.mint(&p2, &500);
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &500);
+
+    // 800 fixed, half of the remaining 200, the rest to the creator
+    assert_eq!(token_client.balance(&restaurant), 800);
+    assert_eq!(token_client.balance(&tip_jar), 100);
+    assert_eq!(token_client.balance(&creator), 100);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Released);
+}
+
+// ============================================
+// Vesting Tests
+// ============================================
+
+#[test]
+fn test_vesting_schedule_validation() {
+    let valid = types::VestingSchedule { start: 1000, cliff: 1500, end: 3000 };
+    assert!(valid.validate().is_ok());
+
+    let inverted = types::VestingSchedule { start: 3000, cliff: 3000, end: 1000 };
+    assert!(inverted.validate().is_err());
+
+    let cliff_after_end = types::VestingSchedule { start: 1000, cliff: 4000, end: 3000 };
+    assert!(cliff_after_end.validate().is_err());
+}
+
+#[test]
+fn test_vesting_schedule_vested_amount() {
+    let schedule = types::VestingSchedule { start: 1000, cliff: 1500, end: 2000 };
+
+    // Nothing before the cliff, even though time has elapsed since start
+    assert_eq!(schedule.vested_amount(1000, 1499), 0);
+    // At the cliff everything accrued since start unlocks
+    assert_eq!(schedule.vested_amount(1000, 1500), 500);
+    assert_eq!(schedule.vested_amount(1000, 1750), 750);
+    assert_eq!(schedule.vested_amount(1000, 2000), 1000);
+    assert_eq!(schedule.vested_amount(1000, 5000), 1000);
+}
+
+#[test]
+fn test_vested_claims_and_pro_rata_refunds() {
+    let env = Env::default();
+    let creator = Address::generate(&env);
+    let asset = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+
+    let mut participants = Vec::new(&env);
+    participants.push_back(EscrowParticipant::new(p1, asset.clone(), 600));
+    participants.push_back(EscrowParticipant::new(p2, asset.clone(), 400));
+
+    let mut escrow = types::create_escrow(
+        &env,
+        String::from_str(&env, "rent"),
+        creator,
+        String::from_str(&env, "Rent"),
+        1000,
+        participants,
+        99999999,
+    );
+    escrow.release_schedule = types::ReleaseSchedule::Vesting(types::VestingSchedule { start: 0, cliff: 0, end: 1000 });
+    escrow.record_asset_deposit(&asset, 1000);
+
+    // A quarter of the way through, a quarter is claimable
+    let claimable = escrow.vested_unreleased_by_asset(&env, 250);
+    assert_eq!(claimable.get(asset.clone()), Some(250));
+    escrow.record_asset_release(&asset, 250);
+    assert_eq!(escrow.vested_unreleased_by_asset(&env, 250).len(), 0);
+
+    // Cancelled here: the unvested 750 goes back pro rata to what each paid
+    assert_eq!(escrow.refundable_amount(&asset, 600), 450);
+    escrow.record_asset_refund(&asset, 600, 450);
+    assert_eq!(escrow.refundable_amount(&asset, 400), 300);
+}
+
+#[test]
+fn test_vested_claims_stop_once_underfunded_escrow_expires() {
+    let (env, client, split_id, creator, p1, _p2, token) = setup_split();
+    let now = env.ledger().timestamp();
+    client.set_vesting(&split_id, &types::VestingSchedule { start: now, cliff: now, end: now + 7200 });
+    client.deposit(&split_id, &p1, &500);
+
+    // A quarter of the way through, a quarter of what's held is claimable
+    env.ledger().with_mut(|li| li.timestamp += 1800);
+    assert_eq!(client.claim_vested(&split_id), 125);
+
+    // After the cutoff the rest belongs to the refund path alone
+    env.ledger().with_mut(|li| li.timestamp += 1801);
+    assert_eq!(client.try_claim_vested(&split_id), Err(Ok(Error::SplitNotFunded)));
+    assert_eq!(client.claim_refund(&split_id, &p1), 375);
+    assert_eq!(client.try_claim_vested(&split_id), Err(Ok(Error::SplitNotFunded)));
+    assert_eq!(token.balance(&creator), 125);
+    assert_eq!(token.balance(&p1), 375);
+}
+
+// ============================================
+// Recurring Escrow Tests
+// ============================================
+
+#[test]
+fn test_recurring_escrow_opens_next_cycle() {
+    let env = Env::default();
+    let creator = Address::generate(&env);
+    let asset = Address::generate(&env);
+    let p1 = Address::generate(&env);
+
+    let mut participants = Vec::new(&env);
+    participants.push_back(EscrowParticipant::new(p1.clone(), asset.clone(), 1000));
+
+    let mut escrow = types::create_escrow(
+        &env,
+        String::from_str(&env, "rent-monthly"),
+        creator,
+        String::from_str(&env, "Rent"),
+        1000,
+        participants,
+        5000,
+    );
+    escrow.recurrence = types::Recurrence::Recurring(types::RecurrenceConfig {
+        interval: 2_592_000,
+        max_cycles: 2,
+        current_cycle: 0,
+    });
+    assert!(escrow.has_next_cycle());
+
+    let mut paid = escrow.participants.get(0).unwrap();
+    paid.amount_paid = 1000;
+    paid.paid_at = Some(4000);
+    escrow.participants.set(0, paid);
+    escrow.amount_collected = 1000;
+    escrow.record_asset_deposit(&asset, 1000);
+    escrow.status = EscrowStatus::Released;
+
+    escrow.open_next_cycle(&env);
+
+    let recurrence = escrow.recurrence_config().unwrap();
+    assert_eq!(recurrence.current_cycle, 1);
+    assert_eq!(escrow.deadline, 5000 + 2_592_000);
+    assert_eq!(escrow.status, EscrowStatus::Active);
+    assert_eq!(escrow.amount_collected, 0);
+    assert_eq!(escrow.asset_collected.len(), 0);
+
+    let carried = escrow.participants.get(0).unwrap();
+    assert_eq!(carried.address, p1);
+    assert_eq!(carried.amount_owed, 1000);
+    assert_eq!(carried.amount_paid, 0);
+    assert!(carried.paid_at.is_none());
+
+    // Second of two cycles is the last one
+    assert!(!escrow.has_next_cycle());
+}
+
+#[test]
+fn test_cycle_record_storage() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SplitEscrowContract);
+    let split_id = String::from_str(&env, "rent-monthly");
+
+    env.as_contract(&contract_id, || {
+        assert!(storage::get_cycle_record(&env, &split_id, 0).is_none());
+
+        let record = types::CycleRecord {
+            cycle: 0,
+            deadline: 5000,
+            total_amount: 1000,
+            amount_collected: 400,
+            status: EscrowStatus::Expired,
+            closed_at: 5001,
+        };
+        storage::set_cycle_record(&env, &split_id, &record);
+
+        let stored = storage::get_cycle_record(&env, &split_id, 0).unwrap();
+        assert_eq!(stored.amount_collected, 400);
+        assert_eq!(stored.status, EscrowStatus::Expired);
+        assert!(storage::get_cycle_record(&env, &split_id, 1).is_none());
+    });
+}
+
+#[test]
+fn test_roll_over_pays_funded_cycle_and_refunds_short_one() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&p1, 500), (&p2, 500)]);
+    client.set_recurrence(&split_id, &1_000, &2);
+    token_admin_client.mint(&p1, &1_000);
+    token_admin_client.mint(&p2, &1_000);
+
+    // Cycle 0 is funded and released as soon as the last share lands
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &500);
+    assert_eq!(token_client.balance(&creator), 1_000);
+
+    // Rolled over long after the deadline: the next cycle still gets a full interval
+    let deadline = client.get_split(&split_id).deadline;
+    env.ledger().set_timestamp(deadline + 5_000);
+    assert_eq!(client.roll_over(&split_id), 0);
+    let escrow = client.get_split(&split_id);
+    assert_eq!(escrow.deadline, deadline + 5_000 + 1_000);
+    assert_eq!(escrow.status, EscrowStatus::Active);
+
+    // Cycle 1 falls short, so the partial payment is refunded
+    client.deposit(&split_id, &p1, &500);
+    assert_eq!(token_client.balance(&p1), 0);
+    env.ledger().set_timestamp(escrow.deadline + 1);
+    assert_eq!(client.roll_over(&split_id), 1);
+
+    assert_eq!(token_client.balance(&p1), 500);
+    assert_eq!(token_client.balance(&p2), 500);
+    assert_eq!(token_client.balance(&creator), 1_000);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Expired);
+    assert_eq!(client.get_cycle_history(&split_id).len(), 2);
+    assert!(client.try_roll_over(&split_id).is_err());
+}
+
+// ============================================
+// Auto-Collection Tests
+// ============================================
+
+#[test]
+fn test_participants_default_to_manual_deposits() {
+    let env = Env::default();
+    let participant = EscrowParticipant::new(Address::generate(&env), Address::generate(&env), 100);
+    assert!(!participant.auto_collect);
+}
+
+#[test]
+fn test_collection_failure_storage() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SplitEscrowContract);
+    let split_id = String::from_str(&env, "rent-monthly");
+    let participant = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        assert!(storage::get_collection_failure(&env, &split_id, &participant).is_none());
+
+        let failure = types::CollectionFailure {
+            participant: participant.clone(),
+            amount_due: 500,
+            reason: types::CollectionFailureReason::InsufficientAllowance,
+            attempted_at: 1234,
+        };
+        storage::set_collection_failure(&env, &split_id, &failure);
+
+        let stored = storage::get_collection_failure(&env, &split_id, &participant).unwrap();
+        assert_eq!(stored.amount_due, 500);
+        assert_eq!(stored.reason, types::CollectionFailureReason::InsufficientAllowance);
+
+        storage::remove_collection_failure(&env, &split_id, &participant);
+        assert!(storage::get_collection_failure(&env, &split_id, &participant).is_none());
+    });
+}
+
+/// Create an auto-collected split that falls due two days out
+fn create_auto_collect_split(
+    env: &Env,
+    client: &SplitEscrowContractClient,
+    token: &Address,
+    creator: &Address,
+    participants: &[&Address],
+) -> String {
+    let mut addresses = Vec::new(env);
+    let mut amounts = Vec::new(env);
+    let mut assets = Vec::new(env);
+    for participant in participants.iter() {
+        addresses.push_back((*participant).clone());
+        amounts.push_back(500);
+        assets.push_back(token.clone());
+    }
+    let split_id = client.create_split(
+        creator,
+        &String::from_str(env, "Rent"),
+        &(500 * participants.len() as i128),
+        &addresses,
+        &amounts,
+        &assets,
+        &Vec::new(env),
+        &(env.ledger().timestamp() + 2 * types::SECONDS_PER_DAY),
+    );
+    for participant in participants.iter() {
+        client.set_auto_collect(&split_id, participant, &true);
+    }
+    split_id
+}
+
+#[test]
+fn test_collect_due_waits_for_due_time_and_skips_missing_allowance() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let approved = Address::generate(&env);
+    let unapproved = Address::generate(&env);
+    token_admin_client.mint(&approved, &500);
+    token_admin_client.mint(&unapproved, &500);
+
+    let split_id = create_auto_collect_split(&env, &client, &token_id, &creator, &[&approved, &unapproved]);
+    token_client.approve(&approved, &client.address, &500, &(env.ledger().sequence() + 1_000));
+
+    assert_eq!(client.try_collect_due(&split_id), Err(Ok(Error::CollectionNotDue)));
+    assert_eq!(token_client.balance(&approved), 500);
+
+    env.ledger().set_timestamp(client.get_split(&split_id).collection_due_at());
+    assert_eq!(client.collect_due(&split_id), 500);
+
+    assert_eq!(token_client.balance(&approved), 0);
+    assert_eq!(token_client.balance(&unapproved), 500);
+    assert!(client.get_collection_failure(&split_id, &approved).is_none());
+    let failure = client.get_collection_failure(&split_id, &unapproved).unwrap();
+    assert_eq!(failure.reason, types::CollectionFailureReason::InsufficientAllowance);
+    assert_eq!(failure.amount_due, 500);
+
+    let escrow = client.get_split(&split_id);
+    assert_eq!(escrow.amount_collected, 500);
+    assert_eq!(escrow.status, EscrowStatus::Active);
+}
+
+#[test]
+fn test_collect_due_only_surcharges_successful_collections() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let approved = Address::generate(&env);
+    let unapproved = Address::generate(&env);
+    token_admin_client.mint(&approved, &1_000);
+    token_admin_client.mint(&unapproved, &1_000);
+
+    let split_id = create_auto_collect_split(&env, &client, &token_id, &creator, &[&approved, &unapproved]);
+    client.set_late_policy(&split_id, &types::LatePolicy {
+        grace_period: 3 * types::SECONDS_PER_DAY,
+        penalty: types::PenaltySchedule::Flat(25),
+        treasury: None,
+    });
+    token_client.approve(&approved, &client.address, &525, &(env.ledger().sequence() + 1_000));
+
+    env.ledger().set_timestamp(client.get_split(&split_id).deadline + types::SECONDS_PER_DAY);
+    assert_eq!(client.collect_due(&split_id), 525);
+
+    // The failed pull quotes the surcharge but doesn't charge it
+    let failure = client.get_collection_failure(&split_id, &unapproved).unwrap();
+    assert_eq!(failure.amount_due, 525);
+    let escrow = client.get_split(&split_id);
+    let owing = escrow.participants.get(1).unwrap();
+    assert_eq!(owing.amount_owed, 500);
+    assert_eq!(owing.late_fee, 0);
+    assert_eq!(escrow.participants.get(0).unwrap().late_fee, 25);
+    assert_eq!(escrow.total_amount, 1_025);
+
+    token_client.approve(&unapproved, &client.address, &525, &(env.ledger().sequence() + 1_000));
+    assert_eq!(client.collect_due(&split_id), 525);
+    assert_eq!(token_client.balance(&unapproved), 475);
+    assert!(client.get_collection_failure(&split_id, &unapproved).is_none());
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Released);
+}
+
+// ============================================
+// Late Payment Tests
+// ============================================
+
+#[test]
+fn test_late_policy_surcharge() {
+    let flat = types::LatePolicy {
+        grace_period: 3 * types::SECONDS_PER_DAY,
+        penalty: types::PenaltySchedule::Flat(25),
+        treasury: None,
+    };
+    assert!(flat.validate().is_ok());
+    assert_eq!(flat.surcharge(1000, 0), 0);
+    assert_eq!(flat.surcharge(1000, 10), 25);
+
+    let daily = types::LatePolicy {
+        grace_period: 3 * types::SECONDS_PER_DAY,
+        penalty: types::PenaltySchedule::BasisPointsPerDay(100),
+        treasury: None,
+    };
+    // Any started day counts as a full day
+    assert_eq!(daily.surcharge(1000, 1), 10);
+    assert_eq!(daily.surcharge(1000, types::SECONDS_PER_DAY), 10);
+    assert_eq!(daily.surcharge(1000, types::SECONDS_PER_DAY + 1), 20);
+
+    let no_grace = types::LatePolicy {
+        grace_period: 0,
+        penalty: types::PenaltySchedule::Flat(25),
+        treasury: None,
+    };
+    assert!(no_grace.validate().is_err());
+}
+
+#[test]
+fn test_grace_period_extends_cutoff_and_tracks_surcharge() {
+    let env = Env::default();
+    let creator = Address::generate(&env);
+    let asset = Address::generate(&env);
+    let p1 = Address::generate(&env);
+
+    let mut participants = Vec::new(&env);
+    participants.push_back(EscrowParticipant::new(p1, asset.clone(), 1000));
+
+    let mut escrow = types::create_escrow(
+        &env,
+        String::from_str(&env, "late-1"),
+        creator,
+        String::from_str(&env, "Dinner"),
+        1000,
+        participants,
+        10_000,
+    );
+    escrow.deadline_policy = types::DeadlinePolicy::Grace(types::LatePolicy {
+        grace_period: 2 * types::SECONDS_PER_DAY,
+        penalty: types::PenaltySchedule::BasisPointsPerDay(100),
+        treasury: None,
+    });
+
+    assert_eq!(escrow.payment_cutoff(), 10_000 + 2 * types::SECONDS_PER_DAY);
+    assert!(!escrow.is_expired(10_001));
+    assert!(escrow.is_expired(escrow.payment_cutoff() + 1));
+
+    let mut p = escrow.participants.get(0).unwrap();
+    assert_eq!(escrow.apply_late_surcharge(&mut p, 10_000), 0);
+
+    // One day late: 1% of the original share
+    assert_eq!(escrow.apply_late_surcharge(&mut p, 10_001), 10);
+    assert_eq!(p.late_fee, 10);
+    assert_eq!(p.amount_owed, 1010);
+
+    // Same day again: nothing new accrues
+    assert_eq!(escrow.apply_late_surcharge(&mut p, 10_500), 0);
+
+    // Second day: only the increase is added
+    assert_eq!(escrow.apply_late_surcharge(&mut p, 10_000 + types::SECONDS_PER_DAY + 1), 10);
+    assert_eq!(p.late_fee, 20);
+    assert_eq!(p.amount_owed, 1020);
+
+    escrow.participants.set(0, p);
+    assert_eq!(escrow.late_fees_by_asset(&env).get(asset), Some(20));
+}
+
+// ============================================
+// Amendment Tests
+// ============================================
+
+#[test]
+fn test_amendment_affected_participants() {
+    let env = Env::default();
+    let creator = Address::generate(&env);
+    let asset = Address::generate(&env);
+    let other_asset = Address::generate(&env);
+    let stays = Address::generate(&env);
+    let leaves = Address::generate(&env);
+    let switches = Address::generate(&env);
+    let joins = Address::generate(&env);
+
+    let mut participants = Vec::new(&env);
+    participants.push_back(EscrowParticipant::new(stays.clone(), asset.clone(), 300));
+    participants.push_back(EscrowParticipant::new(leaves.clone(), asset.clone(), 300));
+    participants.push_back(EscrowParticipant::new(switches.clone(), asset.clone(), 400));
+
+    let escrow = types::create_escrow(
+        &env,
+        String::from_str(&env, "trip"),
+        creator,
+        String::from_str(&env, "Trip"),
+        1000,
+        participants,
+        99999999,
+    );
+
+    let mut shares = Vec::new(&env);
+    shares.push_back(types::ParticipantShare { address: stays.clone(), asset: asset.clone(), amount_owed: 300 });
+    shares.push_back(types::ParticipantShare { address: switches.clone(), asset: other_asset, amount_owed: 400 });
+    shares.push_back(types::ParticipantShare { address: joins.clone(), asset: asset.clone(), amount_owed: 300 });
+
+    let affected = escrow.affected_by(&env, &shares);
+    assert_eq!(affected.len(), 3);
+    assert!(affected.contains(&switches));
+    assert!(affected.contains(&joins));
+    assert!(affected.contains(&leaves));
+    assert!(!affected.contains(&stays));
+}
+
+#[test]
+fn test_amendment_approval_tracking() {
+    let env = Env::default();
+    let creator = Address::generate(&env);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+
+    let mut required = Vec::new(&env);
+    required.push_back(a.clone());
+    required.push_back(b.clone());
+
+    let mut amendment = types::Amendment {
+        proposer: creator,
+        shares: Vec::new(&env),
+        required_approvals: required,
+        approvals: Vec::new(&env),
+        proposed_at: 0,
+    };
+    assert!(!amendment.is_fully_approved());
+
+    amendment.approvals.push_back(a);
+    assert!(!amendment.is_fully_approved());
+
+    amendment.approvals.push_back(b);
+    assert!(amendment.is_fully_approved());
+}
+
+#[test]
+#[should_panic(expected = "Duplicate participant")]
+fn test_create_split_rejects_duplicate_participants() {
+    let (env, admin, token_id, client, _token, _token_admin) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    create_test_split(&env, &client, &token_id, &creator, &[(&p1, 500), (&p1, 500)]);
+}
+
+#[test]
+#[should_panic(expected = "Duplicate participant")]
+fn test_amendment_rejects_duplicate_participants() {
+    let (env, client, split_id, _creator, p1, p2, token) = setup_split();
+
+    // Dropping p2 needs their approval, at which point the amendment applies
+    let mut shares = Vec::new(&env);
+    shares.push_back(types::ParticipantShare { address: p1.clone(), asset: token.address.clone(), amount_owed: 500 });
+    shares.push_back(types::ParticipantShare { address: p1, asset: token.address.clone(), amount_owed: 500 });
+    client.propose_amendment(&split_id, &shares);
+    client.approve_amendment(&split_id, &p2);
+}
+
+// ============================================
+// Overpayment Credit Tests
+// ============================================
+
+#[test]
+fn test_escrow_defaults_to_rejecting_overpayments() {
+    let env = Env::default();
+    let escrow = types::create_escrow(
+        &env,
+        String::from_str(&env, "over-0"),
+        Address::generate(&env),
+        String::from_str(&env, "Dinner"),
+        0,
+        Vec::new(&env),
+        99999999,
+    );
+    assert_eq!(escrow.overpayment_policy, types::OverpaymentPolicy::Reject);
+}
+
+#[test]
+fn test_credit_storage() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SplitEscrowContract);
+    let owner = Address::generate(&env);
+    let usdc = Address::generate(&env);
+    let xlm = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_credit(&env, &owner, &usdc), 0);
+
+        assert_eq!(storage::add_credit(&env, &owner, &usdc, 30), 30);
+        assert_eq!(storage::add_credit(&env, &owner, &usdc, 12), 42);
+        assert_eq!(storage::get_credit(&env, &owner, &xlm), 0);
+
+        storage::set_credit(&env, &owner, &usdc, 0);
+        assert_eq!(storage::get_credit(&env, &owner, &usdc), 0);
+    });
+}
+
+// ============================================
+// Pay-on-Behalf Tests
+// ============================================
+
+fn payment(env: &Env, payer: &Address, beneficiary: &Address, amount: i128) -> types::PaymentRecord {
+    types::PaymentRecord {
+        payer: payer.clone(),
+        beneficiary: beneficiary.clone(),
+        amount,
+        timestamp: env.ledger().timestamp(),
+    }
+}
+
+#[test]
+fn test_payer_refunds_split_by_payer() {
+    let env = Env::default();
+    let beneficiary = Address::generate(&env);
+    let friend = Address::generate(&env);
+
+    let mut log = Vec::new(&env);
+    log.push_back(payment(&env, &friend, &beneficiary, 60));
+    log.push_back(payment(&env, &beneficiary, &beneficiary, 40));
+
+    let plan = types::payer_refunds(&env, &log, &beneficiary, 100, 100, 50);
+    let mut unwound = 0;
+    let mut refunded = 0;
+    for (payer, unwound_share, refund_share) in plan.iter() {
+        if payer == friend {
+            assert_eq!((unwound_share, refund_share), (60, 30));
+        } else {
+            assert_eq!(payer, beneficiary);
+            assert_eq!((unwound_share, refund_share), (40, 20));
+        }
+        unwound += unwound_share;
+        refunded += refund_share;
+    }
+    assert_eq!((unwound, refunded), (100, 50));
+}
+
+#[test]
+fn test_payer_refunds_unlogged_amount_goes_to_beneficiary() {
+    let env = Env::default();
+    let beneficiary = Address::generate(&env);
+    let friend = Address::generate(&env);
+
+    let mut log = Vec::new(&env);
+    log.push_back(payment(&env, &friend, &beneficiary, 30));
+
+    let plan = types::payer_refunds(&env, &log, &beneficiary, 100, 100, 100);
+    assert_eq!(plan.len(), 2);
+    for (payer, unwound_share, _) in plan.iter() {
+        let expected = if payer == friend { 30 } else { 70 };
+        assert_eq!(unwound_share, expected);
+    }
+}
+
+#[test]
+fn test_payer_refunds_skip_unwound_entries() {
+    let env = Env::default();
+    let beneficiary = Address::generate(&env);
+    let friend = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let mut log = Vec::new(&env);
+    log.push_back(payment(&env, &friend, &beneficiary, 50));
+    log.push_back(payment(&env, &friend, &beneficiary, -50));
+    log.push_back(payment(&env, &friend, &other, 25));
+    log.push_back(payment(&env, &beneficiary, &beneficiary, 20));
+
+    let plan = types::payer_refunds(&env, &log, &beneficiary, 20, 20, 20);
+    assert_eq!(plan.len(), 1);
+    assert_eq!(plan.get(0).unwrap(), (beneficiary.clone(), 20, 20));
+}
+
+#[test]
+fn test_payment_log_storage() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SplitEscrowContract);
+    let split_id = String::from_str(&env, "log-1");
+    let payer = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_payment_log(&env, &split_id).len(), 0);
+
+        storage::add_payment_record(&env, &split_id, &payment(&env, &payer, &beneficiary, 10));
+        storage::add_payment_record(&env, &split_id, &payment(&env, &payer, &beneficiary, -4));
+
+        let log = storage::get_payment_log(&env, &split_id);
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.get(1).unwrap().amount, -4);
+    });
+}
+
+#[test]
+fn test_deposit_for_refunds_the_payer() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let friend = Address::generate(&env);
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&p1, 1000)]);
+    token_admin_client.mint(&friend, &600);
+    token_admin_client.mint(&p1, &200);
+
+    client.deposit_for(&split_id, &friend, &p1, &600);
+    client.deposit(&split_id, &p1, &200);
+    assert_eq!(client.get_split(&split_id).participants.get(0).unwrap().amount_paid, 800);
+
+    client.cancel_split(&split_id);
+    assert_eq!(client.claim_refund(&split_id, &p1), 800);
+
+    // Each payer gets back what they put in
+    assert_eq!(token_client.balance(&friend), 600);
+    assert_eq!(token_client.balance(&p1), 200);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+// ============================================
+// Line Item Tests
+// ============================================
+
+fn line_item(env: &Env, unit_price: i128, quantity: u32, assignees: &[(&Address, u32)]) -> types::LineItem {
+    let mut assignments = Vec::new(env);
+    for (participant, weight) in assignees.iter() {
+        assignments.push_back(types::ItemAssignment {
+            participant: (*participant).clone(),
+            weight: *weight,
+        });
+    }
+    types::LineItem {
+        description: String::from_str(env, "Item"),
+        unit_price,
+        quantity,
+        assignments,
+    }
+}
+
+#[test]
+fn test_shares_from_line_items() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let participants = Vec::from_array(&env, [alice.clone(), bob.clone()]);
+
+    let mut items = Vec::new(&env);
+    // Pizza shared 2:1, wine for Bob only
+    items.push_back(line_item(&env, 300, 1, &[(&alice, 2), (&bob, 1)]));
+    items.push_back(line_item(&env, 50, 2, &[(&bob, 1)]));
+
+    let shares = types::shares_from_line_items(&env, &items, &participants).unwrap();
+    assert_eq!(shares, Vec::from_array(&env, [200, 200]));
+}
+
+#[test]
+fn test_shares_from_line_items_rounding_is_deterministic() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let participants = Vec::from_array(&env, [alice.clone(), bob.clone(), carol.clone()]);
+
+    let mut items = Vec::new(&env);
+    items.push_back(line_item(&env, 100, 1, &[(&carol, 1), (&alice, 1), (&bob, 1)]));
+
+    // 33 each, the leftover unit goes to the first assignee listed
+    let shares = types::shares_from_line_items(&env, &items, &participants).unwrap();
+    assert_eq!(shares, Vec::from_array(&env, [33, 33, 34]));
+    assert_eq!(types::shares_from_line_items(&env, &items, &participants).unwrap(), shares);
+}
+
+#[test]
+fn test_shares_from_line_items_rejects_invalid_items() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let participants = Vec::from_array(&env, [alice.clone()]);
+
+    let mut unassigned = Vec::new(&env);
+    unassigned.push_back(line_item(&env, 100, 1, &[]));
+    assert!(types::shares_from_line_items(&env, &unassigned, &participants).is_err());
+
+    let mut zero_weight = Vec::new(&env);
+    zero_weight.push_back(line_item(&env, 100, 1, &[(&alice, 0)]));
+    assert!(types::shares_from_line_items(&env, &zero_weight, &participants).is_err());
+
+    let mut outsider = Vec::new(&env);
+    outsider.push_back(line_item(&env, 100, 1, &[(&stranger, 1)]));
+    assert!(types::shares_from_line_items(&env, &outsider, &participants).is_err());
+
+    let mut free = Vec::new(&env);
+    free.push_back(line_item(&env, 0, 1, &[(&alice, 1)]));
+    assert!(types::shares_from_line_items(&env, &free, &participants).is_err());
+}
+
+#[test]
+fn test_create_split_from_line_items() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+
+    // Two shared pizzas and a bottle of wine only p1 drank
+    let mut items = Vec::new(&env);
+    items.push_back(line_item(&env, 300, 2, &[(&p1, 1), (&p2, 1)]));
+    items.push_back(line_item(&env, 400, 1, &[(&p1, 1)]));
+
+    let split_id = client.create_split(
+        &creator,
+        &String::from_str(&env, "Itemised dinner"),
+        &1000,
+        &Vec::from_array(&env, [p1.clone(), p2.clone()]),
+        &Vec::new(&env),
+        &Vec::from_array(&env, [token_id.clone(), token_id.clone()]),
+        &items,
+        &(env.ledger().timestamp() + 3_600),
+    );
+
+    let escrow = client.get_split(&split_id);
+    assert_eq!(escrow.line_items.len(), 2);
+    assert_eq!(escrow.participants.get(0).unwrap().amount_owed, 700);
+    assert_eq!(escrow.participants.get(1).unwrap().amount_owed, 300);
+
+    token_admin_client.mint(&p1, &700);
+    token_admin_client.mint(&p2, &300);
+    client.deposit(&split_id, &p1, &700);
+    client.deposit(&split_id, &p2, &300);
+
+    assert_eq!(token_client.balance(&creator), 1000);
+    assert_eq!(token_client.balance(&p1), 0);
+    assert_eq!(token_client.balance(&p2), 0);
+}
+
+// ============================================
+// Atomic Swap Tests
+// ============================================
+
+fn setup_swap() -> (
+    Env,
+    SplitEscrowContractClient<'static>,
+    Address,
+    Address,
+    token::Client<'static>,
+    token::Client<'static>,
+    BytesN<32>,
+) {
+    // Alice swaps her 500 of the split's token for Bob's 500 of a second one
+    let (env, client, _split_id, _creator, alice, bob, client_a) = setup_split();
+    let token_b = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    client.add_approved_asset(&client.get_admin(), &token_b);
+    token::StellarAssetClient::new(&env, &token_b).mint(&bob, &500);
+
+    let hash_lock: BytesN<32> = env
+        .crypto()
+        .sha256(&Bytes::from_slice(&env, b"open sesame"))
+        .to_bytes();
+
+    let client_b = token::Client::new(&env, &token_b);
+    (env, client, alice, bob, client_a, client_b, hash_lock)
+}
+
+#[test]
+fn test_atomic_swap_claim_with_secret() {
+    let (env, client, alice, bob, token_a, token_b, hash_lock) = setup_swap();
+    let deadline = env.ledger().timestamp() + 3600;
+
+    let swap_id = client.create_swap(
+        &alice, &bob, &token_a.address, &500, &token_b.address, &500, &hash_lock, &deadline,
+    );
+    assert_eq!(token_a.balance(&alice), 0);
+
+    client.accept_swap(&swap_id);
+    assert_eq!(token_b.balance(&bob), 0);
+    assert_eq!(client.get_swap(&swap_id).status, types::SwapStatus::Locked);
+
+    let secret = Bytes::from_slice(&env, b"open sesame");
+    client.claim_swap(&swap_id, &secret);
+
+    // Bob already held 500 of token A as a split participant
+    assert_eq!(token_a.balance(&bob), 1_000);
+    assert_eq!(token_b.balance(&alice), 500);
+
+    let swap = client.get_swap(&swap_id);
+    assert_eq!(swap.status, types::SwapStatus::Completed);
+    assert_eq!(swap.secret, Some(secret));
+}
+
+#[test]
+fn test_atomic_swap_rejects_wrong_secret() {
+    let (env, client, alice, bob, token_a, token_b, hash_lock) = setup_swap();
+    let deadline = env.ledger().timestamp() + 3600;
+
+    let swap_id = client.create_swap(
+        &alice, &bob, &token_a.address, &500, &token_b.address, &500, &hash_lock, &deadline,
+    );
+    client.accept_swap(&swap_id);
+
+    let result = client.try_claim_swap(&swap_id, &Bytes::from_slice(&env, b"guess"));
+    assert_eq!(result, Err(Ok(Error::SecretInvalid)));
+}
+
+#[test]
+fn test_atomic_swap_refund_after_time_lock() {
+    let (env, client, alice, bob, token_a, token_b, hash_lock) = setup_swap();
+    let deadline = env.ledger().timestamp() + 3600;
+
+    let swap_id = client.create_swap(
+        &alice, &bob, &token_a.address, &500, &token_b.address, &500, &hash_lock, &deadline,
+    );
+    client.accept_swap(&swap_id);
+
+    // Too early to refund
+    assert_eq!(client.try_refund_swap(&swap_id, &alice), Err(Ok(Error::InvalidSwapStatus)));
+
+    env.ledger().with_mut(|li| li.timestamp = deadline);
+
+    // Too late to claim
+    let secret = Bytes::from_slice(&env, b"open sesame");
+    assert_eq!(client.try_claim_swap(&swap_id, &secret), Err(Ok(Error::SwapExpired)));
+
+    client.refund_swap(&swap_id, &alice);
+    assert_eq!(token_a.balance(&alice), 500);
+    assert_eq!(token_b.balance(&bob), 500);
+    assert_eq!(client.get_swap(&swap_id).status, types::SwapStatus::Refunded);
+}
+
+// ============================================
+// Oracle Consensus Tests
+// ============================================
+
+#[test]
+fn test_compute_consensus_median_and_confidence() {
+    let env = Env::default();
+    let pair = String::from_str(&env, "XLM/USDC");
+
+    let odd = types::compute_consensus(&env, &pair, &Vec::from_array(&env, [1_050, 1_000, 990]), 7);
+    assert_eq!(odd.price, 1_000);
+    assert_eq!(odd.participating_oracles, 3);
+    assert_eq!(odd.timestamp, 7);
+    // Mean absolute deviation is 20/1000 = 200 bps
+    assert_eq!(odd.confidence, 9_800);
+
+    let even = types::compute_consensus(&env, &pair, &Vec::from_array(&env, [100, 300, 200, 400]), 7);
+    assert_eq!(even.price, 250);
+
+    let unanimous = types::compute_consensus(&env, &pair, &Vec::from_array(&env, [500, 500]), 7);
+    assert_eq!(unanimous.confidence, 10_000);
+
+    let scattered = types::compute_consensus(&env, &pair, &Vec::from_array(&env, [1, 1_000, 5_000]), 7);
+    assert_eq!(scattered.confidence, 0);
+}
+
+fn setup_oracles(
+    count: usize,
+) -> (Env, SplitEscrowContractClient<'static>, std::vec::Vec<Address>) {
+    let (env, client, _split_id, _creator, _p1, _p2, token) = setup_split();
+    let admin = client.get_admin();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token.address);
+
+    client.set_oracle_config(&admin, &types::OracleConfig {
+        required_verifications: 1,
+        verification_timeout: 3600,
+        min_oracles: 3,
+        oracle_addresses: Vec::new(&env),
+        min_stake: 500,
+        price_staleness: 600,
+    });
+
+    let mut oracles = std::vec::Vec::new();
+    for _ in 0..count {
+        let oracle = Address::generate(&env);
+        token_admin_client.mint(&oracle, &500);
+        client.register_oracle(&oracle, &500);
+        oracles.push(oracle);
+    }
+    (env, client, oracles)
+}
+
+#[test]
+fn test_register_oracle_requires_min_stake() {
+    let (env, client, oracles) = setup_oracles(1);
+
+    let node = client.get_oracle(&oracles[0]);
+    assert_eq!(node.stake, 500);
+    assert!(node.active);
+
+    let cheap = Address::generate(&env);
+    assert_eq!(client.try_register_oracle(&cheap, &499), Err(Ok(Error::InsufficientStake)));
+    assert_eq!(
+        client.try_register_oracle(&oracles[0], &500),
+        Err(Ok(Error::OracleAlreadyRegistered))
+    );
+}
+
+#[test]
+fn test_consensus_needs_min_fresh_oracles() {
+    let (env, client, oracles) = setup_oracles(3);
+    let pair = String::from_str(&env, "XLM/USDC");
+
+    client.submit_price(&oracles[0], &pair, &1_000);
+    client.submit_price(&oracles[1], &pair, &1_020);
+    assert_eq!(client.try_get_consensus_price(&pair), Err(Ok(Error::InsufficientOracles)));
+
+    client.submit_price(&oracles[2], &pair, &990);
+    assert_eq!(client.get_consensus_price(&pair), 1_000);
+
+    // The first two reports go stale; only one fresh oracle remains
+    env.ledger().with_mut(|li| li.timestamp += 601);
+    client.submit_price(&oracles[2], &pair, &995);
+    assert_eq!(client.try_get_consensus_price(&pair), Err(Ok(Error::InsufficientOracles)));
+}
+
+#[test]
+fn test_unregistered_oracle_cannot_submit() {
+    let (env, client, _oracles) = setup_oracles(0);
+    let stranger = Address::generate(&env);
+    let pair = String::from_str(&env, "XLM/USDC");
+
+    assert_eq!(
+        client.try_submit_price(&stranger, &pair, &1_000),
+        Err(Ok(Error::OracleNotRegistered))
+    );
+}
+
+// ============================================
+// Oracle Slashing Tests
+// ============================================
+
+#[test]
+fn test_slashing_config_outlier_band() {
+    let env = Env::default();
+    let config = types::SlashingConfig {
+        deviation_band: 500,
+        slash_rate: 1_000,
+        reputation_penalty: 10,
+        reputation_floor: 50,
+        treasury: Address::generate(&env),
+    };
+    assert!(config.validate().is_ok());
+
+    // 5% band around 1000
+    assert!(!config.is_outlier(1_050, 1_000));
+    assert!(!config.is_outlier(950, 1_000));
+    assert!(config.is_outlier(1_051, 1_000));
+    assert!(config.is_outlier(949, 1_000));
+
+    assert_eq!(config.slash_amount(500), 50);
+
+    let too_harsh = types::SlashingConfig { slash_rate: 10_001, ..config.clone() };
+    assert!(too_harsh.validate().is_err());
+}
+
+#[test]
+fn test_outlier_oracle_is_slashed_and_deactivated() {
+    let (env, client, oracles) = setup_oracles(4);
+    let pair = String::from_str(&env, "XLM/USDC");
+    let treasury = Address::generate(&env);
+
+    client.set_slashing_config(&client.get_admin(), &types::SlashingConfig {
+        deviation_band: 500,
+        slash_rate: 1_000,
+        reputation_penalty: 60,
+        reputation_floor: 50,
+        treasury: treasury.clone(),
+    });
+
+    client.submit_price(&oracles[0], &pair, &1_000);
+    client.submit_price(&oracles[1], &pair, &1_010);
+    client.submit_price(&oracles[2], &pair, &990);
+    client.submit_price(&oracles[3], &pair, &2_000);
+
+    // Honest oracles are untouched
+    for oracle in oracles[..3].iter() {
+        let node = client.get_oracle(oracle);
+        assert_eq!(node.stake, 500);
+        assert_eq!(node.reputation, 100);
+    }
+
+    let outlier = client.get_oracle(&oracles[3]);
+    assert_eq!(outlier.stake, 450);
+    assert_eq!(outlier.reputation, 40);
+    assert!(!outlier.active);
+
+    let token = token::Client::new(&env, &client.get_token());
+    assert_eq!(token.balance(&treasury), 50);
+
+    // The deactivated feed no longer counts and can no longer report
+    assert_eq!(client.get_consensus_price(&pair), 1_000);
+    assert_eq!(
+        client.try_submit_price(&oracles[3], &pair, &1_000),
+        Err(Ok(Error::OracleNotRegistered))
+    );
+}
+
+// ============================================
+// Verification Quorum Tests
+// ============================================
+
+fn setup_verification() -> (
+    Env,
+    SplitEscrowContractClient<'static>,
+    String,
+    Address,
+    std::vec::Vec<Address>,
+    Address,
+    Address,
+) {
+    let (env, client, split_id, creator, p1, p2, _token) = setup_split();
+    let admin = client.get_admin();
+
+    let oracles: std::vec::Vec<Address> = (0..3).map(|_| Address::generate(&env)).collect();
+    let mut oracle_addresses = Vec::new(&env);
+    for oracle in oracles.iter() {
+        oracle_addresses.push_back(oracle.clone());
+    }
+    client.set_oracle_config(&admin, &types::OracleConfig {
+        required_verifications: 2,
+        verification_timeout: 3600,
+        min_oracles: 1,
+        oracle_addresses,
+        min_stake: 1,
+        price_staleness: 600,
+    });
+
+    (env, client, split_id, creator, oracles, p1, p2)
+}
+
+/// Fund the verification fixture's escrow; flagged escrows wait as Completed
+fn fund_verification_split(client: &SplitEscrowContractClient, split_id: &String, p1: &Address, p2: &Address) {
+    client.deposit(split_id, p1, &500);
+    client.deposit(split_id, p2, &500);
+}
+
+#[test]
+fn test_verification_reaches_quorum() {
+    let (env, client, split_id, creator, oracles, _p1, _p2) = setup_verification();
+    let receipt = String::from_str(&env, "receipt-hash");
+
+    let verification_id = client.submit_verification(&split_id, &creator, &receipt);
+    assert_eq!(
+        client.try_submit_verification(&split_id, &creator, &receipt),
+        Err(Ok(Error::VerificationAlreadyExists))
+    );
+
+    assert_eq!(client.verify_split(&verification_id, &oracles[0], &true), types::VerificationStatus::Pending);
+    assert_eq!(
+        client.try_verify_split(&verification_id, &oracles[0], &true),
+        Err(Ok(Error::AlreadyAttested))
+    );
+    assert_eq!(
+        client.try_verify_split(&verification_id, &Address::generate(&env), &true),
+        Err(Ok(Error::OracleNotAuthorized))
+    );
+
+    // A dissenting vote doesn't stop the quorum
+    client.verify_split(&verification_id, &oracles[1], &false);
+    assert_eq!(client.verify_split(&verification_id, &oracles[2], &true), types::VerificationStatus::Verified);
+
+    let request = client.get_verification(&verification_id);
+    assert_eq!(request.approvals.len(), 2);
+    assert_eq!(request.rejections.len(), 1);
+    assert_eq!(request.verified_by, Some(oracles[2].clone()));
+}
+
+#[test]
+fn test_pending_and_rejected_verification_block_release() {
+    let (env, client, split_id, creator, oracles, p1, p2) = setup_verification();
+    let verification_id = client.submit_verification(&split_id, &creator, &String::from_str(&env, "receipt-hash"));
+    fund_verification_split(&client, &split_id, &p1, &p2);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Completed);
+
+    assert_eq!(client.try_release_funds(&split_id), Err(Ok(Error::VerificationPending)));
+
+    client.verify_split(&verification_id, &oracles[0], &false);
+    client.verify_split(&verification_id, &oracles[1], &false);
+    assert_eq!(client.get_verification(&verification_id).status, types::VerificationStatus::Rejected);
+
+    assert_eq!(client.try_release_funds(&split_id), Err(Ok(Error::VerificationRejected)));
+}
+
+#[test]
+fn test_rejected_verification_refunds_participants() {
+    let (env, client, split_id, creator, oracles, p1, p2) = setup_verification();
+    let token = token::Client::new(&env, &client.get_token());
+    let verification_id = client.submit_verification(&split_id, &creator, &String::from_str(&env, "receipt-hash"));
+    fund_verification_split(&client, &split_id, &p1, &p2);
+
+    // Nothing to settle while the oracles are still deciding
+    assert_eq!(client.try_settle_verification(&split_id), Err(Ok(Error::EscrowNotRefundable)));
+
+    client.verify_split(&verification_id, &oracles[0], &false);
+    client.verify_split(&verification_id, &oracles[1], &false);
+
+    assert_eq!(client.settle_verification(&split_id), 1000);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Cancelled);
+    assert_eq!(token.balance(&p1), 500);
+    assert_eq!(token.balance(&p2), 500);
+    assert_eq!(token.balance(&creator), 0);
+    assert_eq!(client.try_settle_verification(&split_id), Err(Ok(Error::EscrowNotRefundable)));
+}
+
+#[test]
+fn test_verification_times_out() {
+    let (env, client, split_id, creator, oracles, _p1, _p2) = setup_verification();
+    let verification_id = client.submit_verification(&split_id, &creator, &String::from_str(&env, "receipt-hash"));
+    client.verify_split(&verification_id, &oracles[0], &true);
+
+    assert_eq!(client.try_expire_verification(&verification_id), Err(Ok(Error::InvalidVerificationStatus)));
+
+    env.ledger().with_mut(|li| li.timestamp += 3601);
+    assert_eq!(client.get_verification(&verification_id).status, types::VerificationStatus::Expired);
+    assert_eq!(
+        client.try_verify_split(&verification_id, &oracles[1], &true),
+        Err(Ok(Error::InvalidVerificationStatus))
+    );
+
+    client.expire_verification(&verification_id);
+    assert_eq!(client.get_verification(&verification_id).status, types::VerificationStatus::Expired);
+}
+
+#[test]
+fn test_repeated_flags_cannot_hold_funds_forever() {
+    let (env, client, split_id, creator, _oracles, p1, p2) = setup_verification();
+    let receipt = String::from_str(&env, "receipt-hash");
+
+    for i in 0..types::MAX_VERIFICATION_FLAGS {
+        client.submit_verification(&split_id, &creator, &receipt);
+        if i == 0 {
+            fund_verification_split(&client, &split_id, &p1, &p2);
+        }
+        assert_eq!(client.try_release_funds(&split_id), Err(Ok(Error::VerificationPending)));
+        env.ledger().with_mut(|li| li.timestamp += 3601);
+    }
+
+    assert_eq!(
+        client.try_submit_verification(&split_id, &creator, &receipt),
+        Err(Ok(Error::FlagLimitReached))
+    );
+    client.release_funds(&split_id);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Released);
+}
+
+// ============================================
+// Insurance Lifecycle Tests
+// ============================================
+
+#[test]
+fn test_insurance_premium_rounds_up() {
+    let config = types::InsuranceConfig {
+        premium_rate: 250,
+        claim_window: 86_400,
+    };
+    assert!(config.validate().is_ok());
+    assert_eq!(config.premium_for(1_000), 25);
+    assert_eq!(config.premium_for(1_001), 26);
+
+    let free = types::InsuranceConfig { premium_rate: 0, ..config };
+    assert!(free.validate().is_err());
+}
+
+#[test]
+fn test_format_number_as_string() {
+    let env = Env::default();
+    assert_eq!(storage::format_number_as_string(&env, 0), String::from_str(&env, "0"));
+    assert_eq!(storage::format_number_as_string(&env, 42), String::from_str(&env, "42"));
+    assert_eq!(
+        storage::format_number_as_string(&env, u64::MAX),
+        String::from_str(&env, "18446744073709551615")
+    );
+}
+
+fn setup_insurance() -> (
+    Env,
+    SplitEscrowContractClient<'static>,
+    Address,
+    String,
+    Address,
+    token::Client<'static>,
+    Address,
+) {
+    let (env, client, split_id, creator, payer, _defaulter, token_client) = setup_split();
+    let admin = client.get_admin();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_client.address);
+    client.set_insurance_config(&types::InsuranceConfig {
+        premium_rate: 200,
+        claim_window: 86_400,
+    });
+
+    // One participant pays their half, the other defaults
+    client.deposit(&split_id, &payer, &500);
+
+    token_admin_client.mint(&creator, &100);
+    let backer = Address::generate(&env);
+    token_admin_client.mint(&backer, &1_000);
+    client.fund_premium_pool(&backer, &1_000);
+
+    (env, client, admin, split_id, creator, token_client, payer)
+}
+
+#[test]
+fn test_purchase_insurance_charges_premium() {
+    let (_env, client, _admin, split_id, creator, token, _payer) = setup_insurance();
+
+    let insurance_id = client.purchase_insurance(&split_id, &1_000);
+    assert_eq!(token.balance(&creator), 80);
+    assert_eq!(client.get_premium_pool(), 1_020);
+
+    let policy = client.get_insurance(&insurance_id);
+    assert_eq!(policy.premium, 20);
+    assert_eq!(policy.policy_holder, creator);
+    assert_eq!(client.get_split_insurance(&split_id), Some(insurance_id));
+
+    assert_eq!(
+        client.try_purchase_insurance(&split_id, &500),
+        Err(Ok(Error::InsuranceAlreadyExists))
+    );
+}
+
+#[test]
+fn test_filed_claim_is_processed_and_paid() {
+    let (env, client, admin, split_id, creator, token, _payer) = setup_insurance();
+    let insurance_id = client.purchase_insurance(&split_id, &1_000);
+
+    let claim_id = client.file_claim(&insurance_id, &String::from_str(&env, "Receipt disputed"), &300);
+    assert_eq!(
+        client.try_process_claim(&claim_id, &Address::generate(&env), &true, &None),
+        Err(Ok(Error::OracleNotAuthorized))
+    );
+
+    assert_eq!(client.process_claim(&claim_id, &admin, &true, &None), 300);
+    assert_eq!(client.get_claim(&claim_id).status, types::ClaimStatus::Paid);
+    assert_eq!(token.balance(&creator), 380);
+    assert_eq!(client.get_insurance(&insurance_id).paid_out, 300);
+
+    let rejected = client.file_claim(&insurance_id, &String::from_str(&env, "Second try"), &100);
+    assert_eq!(client.process_claim(&rejected, &admin, &false, &None), 0);
+    assert_eq!(client.get_claim(&rejected).status, types::ClaimStatus::Rejected);
+    assert_eq!(client.get_insurance_claims(&insurance_id).len(), 2);
+}
+
+#[test]
+fn test_default_payout_after_deadline() {
+    let (env, client, _admin, split_id, creator, token, payer) = setup_insurance();
+    let insurance_id = client.purchase_insurance(&split_id, &1_000);
+
+    // Not before the deadline
+    assert_eq!(
+        client.try_claim_default_payout(&split_id),
+        Err(Ok(Error::InvalidClaimStatus))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+    assert_eq!(client.claim_default_payout(&split_id), 500);
+
+    // The shortfall from the pool plus the payer's deposit
+    assert_eq!(token.balance(&creator), 80 + 500 + 500);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Released);
+    assert_eq!(
+        client.try_claim_refund(&split_id, &payer),
+        Err(Ok(Error::EscrowNotRefundable))
+    );
+
+    let policy = client.get_insurance(&insurance_id);
+    assert_eq!(policy.status, types::InsuranceStatus::Claimed);
+    assert_eq!(
+        client.try_claim_default_payout(&split_id),
+        Err(Ok(Error::InvalidClaimStatus))
+    );
+}
+
+#[test]
+fn test_default_payout_limited_to_deposits_held() {
+    let (env, client, _admin, split_id, creator, token, payer) = setup_insurance();
+    client.purchase_insurance(&split_id, &1_000);
+
+    // Once the only deposit is refunded there is nothing left to lose
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+    assert_eq!(client.claim_refund(&split_id, &payer), 500);
+    assert_eq!(
+        client.try_claim_default_payout(&split_id),
+        Err(Ok(Error::NoFundsAvailable))
+    );
+    assert_eq!(token.balance(&creator), 80);
+    assert_eq!(client.get_premium_pool(), 1_020);
+}
+
+#[test]
+fn test_default_payout_refused_when_creator_defaults() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+    client.set_insurance_config(&types::InsuranceConfig {
+        premium_rate: 200,
+        claim_window: 86_400,
+    });
+
+    let creator = Address::generate(&env);
+    let payer = Address::generate(&env);
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&payer, 600), (&creator, 400)]);
+    token_admin_client.mint(&payer, &600);
+    token_admin_client.mint(&creator, &100);
+    client.deposit(&split_id, &payer, &600);
+    client.purchase_insurance(&split_id, &1_000);
+
+    let backer = Address::generate(&env);
+    token_admin_client.mint(&backer, &1_000);
+    client.fund_premium_pool(&backer, &1_000);
+
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+    assert_eq!(
+        client.try_claim_default_payout(&split_id),
+        Err(Ok(Error::InvalidClaimStatus))
+    );
+    assert_eq!(client.claim_refund(&split_id, &payer), 600);
+    assert_eq!(token_client.balance(&payer), 600);
+}
+
+// ============================================
+// Activity Tracking Tests
+// ============================================
+
+#[test]
+fn test_deposits_and_release_record_activity() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &2_000);
+    token_admin_client.mint(&bob, &2_000);
+
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&alice, 2_000), (&bob, 1_000)]);
+    client.deposit(&split_id, &alice, &1_500);
+    client.deposit(&split_id, &alice, &500);
+
+    // Nothing counts until the escrow settles
+    assert!(client.get_user_rewards(&creator).is_none());
+    assert!(client.get_user_rewards(&alice).is_none());
+
+    client.deposit_for(&split_id, &bob, &bob, &1_000);
+
+    // Two deposits, one split
+    let alice_rewards = client.get_user_rewards(&alice).unwrap();
+    assert_eq!(alice_rewards.total_splits_participated, 1);
+    assert_eq!(alice_rewards.total_amount_transacted, 2_000);
+
+    // The last deposit funded the escrow and released it to the creator
+    let creator_rewards = client.get_user_rewards(&creator).unwrap();
+    assert_eq!(creator_rewards.total_splits_created, 1);
+    assert_eq!(creator_rewards.total_amount_transacted, 3_000);
+
+    // 5 for participating plus 0.1% of 2000 volume
+    assert_eq!(client.calculate_rewards(&alice), 7);
+}
+
+#[test]
+fn test_refunded_deposits_record_no_activity() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &500);
+
+    let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&alice, 500), (&bob, 500)]);
+    client.deposit(&split_id, &alice, &500);
+
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+    client.claim_refund(&split_id, &alice);
+
+    assert_eq!(client.calculate_rewards(&creator), 0);
+    assert_eq!(client.calculate_rewards(&alice), 0);
+}
+
+// ============================================
+// Rewards Treasury Tests
+// ============================================
+
+fn setup_rewards(points_user: &[(u64, u64, i128)]) -> (
+    Env,
+    SplitEscrowContractClient<'static>,
+    std::vec::Vec<Address>,
+    token::Client<'static>,
+) {
+    let (env, client, _split_id, _creator, _p1, _p2, _token) = setup_split();
+    let admin = client.get_admin();
+
+    let reward_token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    token::StellarAssetClient::new(&env, &reward_token).mint(&admin, &1_000);
+
+    client.set_rewards_config(&types::RewardsConfig {
+        reward_token: reward_token.clone(),
+        tokens_per_point: 2,
+        epoch_length: 86_400,
+        epoch_cap: 100,
+    });
+    client.fund_rewards_treasury(&admin, &1_000);
+
+    let mut users = std::vec::Vec::new();
+    for (created, participated, volume) in points_user.iter() {
+        let user = Address::generate(&env);
+        let rewards = types::UserRewards {
+            user: user.clone(),
+            total_splits_created: *created,
+            total_splits_participated: *participated,
+            total_amount_transacted: *volume,
+            rewards_earned: 0,
+            rewards_claimed: 0,
+            last_activity: 0,
+            status: types::RewardsStatus::Active,
+        };
+        env.as_contract(&client.address, || storage::set_user_rewards(&env, &user, &rewards));
+        users.push(user);
+    }
+
+    let reward_client = token::Client::new(&env, &reward_token);
+    (env, client, users, reward_client)
+}
+
+#[test]
+fn test_user_rewards_total_points() {
+    let env = Env::default();
+    let rewards = types::UserRewards {
+        user: Address::generate(&env),
+        total_splits_created: 2,
+        total_splits_participated: 3,
+        total_amount_transacted: 4_500,
+        rewards_earned: 0,
+        rewards_claimed: 0,
+        last_activity: 0,
+        status: types::RewardsStatus::Active,
+    };
+    assert_eq!(rewards.total_points(), 20 + 15 + 4);
+}
+
+#[test]
+fn test_claim_rewards_pays_tokens() {
+    let (_env, client, users, reward_token) = setup_rewards(&[(2, 0, 0)]);
+
+    // 20 points at 2 tokens each
+    assert_eq!(client.claim_rewards(&users[0]), 40);
+    assert_eq!(reward_token.balance(&users[0]), 40);
+    assert_eq!(client.get_rewards_treasury(), 960);
+    assert_eq!(client.get_user_rewards(&users[0]).unwrap().rewards_claimed, 20);
+
+    assert_eq!(client.try_claim_rewards(&users[0]), Err(Ok(Error::RewardsAlreadyClaimed)));
+}
+
+#[test]
+fn test_claim_rewards_respects_epoch_cap() {
+    let (env, client, users, reward_token) = setup_rewards(&[(4, 0, 0), (2, 0, 0)]);
+
+    // 40 points would be 80 tokens; the first user takes 80 of the 100 cap
+    assert_eq!(client.claim_rewards(&users[0]), 80);
+    // Only 20 tokens (10 points) left this epoch
+    assert_eq!(client.claim_rewards(&users[1]), 20);
+    assert_eq!(client.try_claim_rewards(&users[1]), Err(Ok(Error::InsufficientRewards)));
+
+    // Next epoch the rest can be claimed
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    assert_eq!(client.claim_rewards(&users[1]), 20);
+    assert_eq!(reward_token.balance(&users[1]), 40);
+}
+
+#[test]
+fn test_suspended_user_cannot_claim() {
+    let (_env, client, users, _reward_token) = setup_rewards(&[(1, 0, 0)]);
+
+    client.set_rewards_status(&users[0], &types::RewardsStatus::Suspended);
+    assert_eq!(client.try_claim_rewards(&users[0]), Err(Ok(Error::RewardsSuspended)));
+
+    client.set_rewards_status(&users[0], &types::RewardsStatus::Active);
+    assert_eq!(client.claim_rewards(&users[0]), 20);
+}
+
+#[test]
+fn test_create_cancel_loops_earn_nothing() {
+    let (env, client, _users, reward_token) = setup_rewards(&[]);
+    let token_id = client.get_token();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let creator = Address::generate(&env);
+    let sock_puppet = Address::generate(&env);
+    token_admin_client.mint(&sock_puppet, &500);
+
+    for _ in 0..5 {
+        let split_id = create_test_split(&env, &client, &token_id, &creator, &[(&sock_puppet, 500)]);
+        client.deposit(&split_id, &sock_puppet, &400);
+        client.cancel_split(&split_id);
+        client.claim_refund(&split_id, &sock_puppet);
+    }
+
+    assert_eq!(client.try_claim_rewards(&creator), Err(Ok(Error::UserNotFound)));
+    assert_eq!(client.try_claim_rewards(&sock_puppet), Err(Ok(Error::UserNotFound)));
+    assert_eq!(client.calculate_rewards(&creator), 0);
+    assert_eq!(client.calculate_rewards(&sock_puppet), 0);
+    assert_eq!(reward_token.balance(&creator), 0);
+    assert_eq!(client.get_rewards_treasury(), 1_000);
+}
+
+// ============================================
+// Cross-Chain Bridge Tests
+// ============================================
+
+/// Deterministic relayer keypair for tests
+fn relayer_keypair(env: &Env, seed: u8) -> (ed25519_dalek::SigningKey, BytesN<32>) {
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[seed; 32]);
+    let public_key = BytesN::from_array(env, &signing_key.verifying_key().to_bytes());
+    (signing_key, public_key)
+}
+
+/// Sign a bridge completion for `contract` the way a relayer would
+fn sign_bridge(
+    env: &Env,
+    contract: &Address,
+    signing_key: &ed25519_dalek::SigningKey,
+    bridge_id: &String,
+    amount: i128,
+    recipient: &String,
+) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+    use soroban_sdk::xdr::ToXdr;
+
+    let message = (
+        symbol_short!("sebridge"),
+        env.ledger().network_id(),
+        contract.clone(),
+        bridge_id.clone(),
+        amount,
+        recipient.clone(),
+    )
+        .to_xdr(env);
+    let mut buf = std::vec![0u8; message.len() as usize];
+    message.copy_into_slice(&mut buf);
+    BytesN::from_array(env, &signing_key.sign(&buf).to_bytes())
+}
+
+fn setup_bridge() -> (
+    Env,
+    SplitEscrowContractClient<'static>,
+    token::Client<'static>,
+    Address,
+    Address,
+    ed25519_dalek::SigningKey,
+    BytesN<32>,
+) {
+    // The split's first participant bridges part of their 500
+    let (env, client, _split_id, _creator, sender, _p2, token_client) = setup_split();
+
+    let (signing_key, relayer) = relayer_keypair(&env, 7);
+    let vault = Address::generate(&env);
+    client.set_bridge_config(&types::BridgeConfig {
+        relayers: Vec::from_array(&env, [relayer.clone()]),
+        refund_timeout: 3600,
+        vault: vault.clone(),
+    });
+
+    (env, client, token_client, sender, vault, signing_key, relayer)
+}
+
+fn start_bridge(env: &Env, client: &SplitEscrowContractClient, sender: &Address, token: &Address) -> String {
+    client.initiate_bridge(
+        sender,
+        token,
+        &400,
+        &String::from_str(env, "stellar"),
+        &String::from_str(env, "ethereum"),
+        &String::from_str(env, "0xabc"),
+    )
+}
+
+#[test]
+fn test_complete_bridge_with_relayer_signature() {
+    let (env, client, token_client, sender, vault, signing_key, relayer) = setup_bridge();
+    let bridge_id = start_bridge(&env, &client, &sender, &token_client.address);
+    assert_eq!(token_client.balance(&sender), 100);
+    assert_eq!(token_client.balance(&client.address), 400);
+
+    let signature = sign_bridge(&env, &client.address, &signing_key, &bridge_id, 400, &String::from_str(&env, "0xabc"));
+    client.complete_bridge(&bridge_id, &relayer, &signature);
+
+    let bridge = client.get_bridge(&bridge_id);
+    assert_eq!(bridge.status, types::BridgeStatus::Completed);
+    assert!(bridge.proof_hash.is_some());
+    assert_eq!(token_client.balance(&vault), 400);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    // Cannot be completed or refunded again
+    assert_eq!(
+        client.try_complete_bridge(&bridge_id, &relayer, &signature),
+        Err(Ok(Error::InvalidBridgeStatus))
+    );
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    assert_eq!(client.try_refund_bridge(&bridge_id), Err(Ok(Error::InvalidBridgeStatus)));
+}
+
+#[test]
+fn test_complete_bridge_rejects_unknown_relayer() {
+    let (env, client, token_client, sender, _vault, _signing_key, _relayer) = setup_bridge();
+    let bridge_id = start_bridge(&env, &client, &sender, &token_client.address);
+
+    let (rogue_key, rogue) = relayer_keypair(&env, 9);
+    let signature = sign_bridge(&env, &client.address, &rogue_key, &bridge_id, 400, &String::from_str(&env, "0xabc"));
+    assert_eq!(
+        client.try_complete_bridge(&bridge_id, &rogue, &signature),
+        Err(Ok(Error::RelayerNotAuthorized))
+    );
+}
+
+#[test]
+fn test_complete_bridge_rejects_signature_over_other_recipient() {
+    let (env, client, token_client, sender, _vault, signing_key, relayer) = setup_bridge();
+    let bridge_id = start_bridge(&env, &client, &sender, &token_client.address);
+
+    let signature = sign_bridge(&env, &client.address, &signing_key, &bridge_id, 400, &String::from_str(&env, "0xdef"));
+    assert!(client.try_complete_bridge(&bridge_id, &relayer, &signature).is_err());
+    assert_eq!(client.get_bridge(&bridge_id).status, types::BridgeStatus::Initiated);
+}
+
+#[test]
+fn test_complete_bridge_rejects_signature_for_other_deployment() {
+    let (env, client, token_client, sender, _vault, signing_key, relayer) = setup_bridge();
+    let bridge_id = start_bridge(&env, &client, &sender, &token_client.address);
+
+    let other_deployment = env.register_contract(None, SplitEscrowContract);
+    let signature = sign_bridge(&env, &other_deployment, &signing_key, &bridge_id, 400, &String::from_str(&env, "0xabc"));
+    assert!(client.try_complete_bridge(&bridge_id, &relayer, &signature).is_err());
+    assert_eq!(client.get_bridge(&bridge_id).status, types::BridgeStatus::Initiated);
+}
+
+#[test]
+fn test_complete_bridge_rejected_once_refundable() {
+    let (env, client, token_client, sender, _vault, signing_key, relayer) = setup_bridge();
+    let bridge_id = start_bridge(&env, &client, &sender, &token_client.address);
+    let signature = sign_bridge(&env, &client.address, &signing_key, &bridge_id, 400, &String::from_str(&env, "0xabc"));
+
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    assert_eq!(
+        client.try_complete_bridge(&bridge_id, &relayer, &signature),
+        Err(Ok(Error::InvalidBridgeStatus))
+    );
+
+    client.refund_bridge(&bridge_id);
+    assert_eq!(client.get_bridge(&bridge_id).status, types::BridgeStatus::Refunded);
+    assert_eq!(token_client.balance(&sender), 500);
+}
+
+#[test]
+fn test_refund_bridge_after_timeout() {
+    let (env, client, token_client, sender, _vault, _signing_key, _relayer) = setup_bridge();
+    let bridge_id = start_bridge(&env, &client, &sender, &token_client.address);
+
+    assert_eq!(client.try_refund_bridge(&bridge_id), Err(Ok(Error::InvalidBridgeStatus)));
+
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    client.refund_bridge(&bridge_id);
+
+    assert_eq!(client.get_bridge(&bridge_id).status, types::BridgeStatus::Refunded);
+    assert_eq!(token_client.balance(&sender), 500);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+// ============================================
+// Pause Group Tests
+// ============================================
+
+#[test]
+fn test_paused_deposits_still_allow_refunds() {
+    let (_env, client, split_id, _creator, p1, p2, token) = setup_split();
+    let admin = client.get_admin();
+    client.deposit(&split_id, &p1, &500);
+
+    client.pause(&admin, &types::PauseGroup::Deposits);
+    assert!(client.is_paused(&types::PauseGroup::Deposits));
+    assert!(!client.is_paused(&types::PauseGroup::Refunds));
+    assert!(client.try_deposit(&split_id, &p2, &500).is_err());
+
+    client.cancel_split(&split_id);
+    assert_eq!(client.claim_refund(&split_id, &p1), 500);
+    assert_eq!(token.balance(&p1), 500);
+
+    client.unpause(&admin, &types::PauseGroup::Deposits);
+    assert!(!client.is_paused(&types::PauseGroup::Deposits));
+}
+
+#[test]
+fn test_paused_releases_hold_funded_escrow() {
+    let (_env, client, split_id, creator, p1, p2, token) = setup_split();
+    let admin = client.get_admin();
+
+    client.pause(&admin, &types::PauseGroup::Releases);
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &500);
+
+    // Fully funded, but nothing leaves the contract while releases are paused
+    assert_eq!(token.balance(&creator), 0);
+    assert!(client.try_release_funds(&split_id).is_err());
+
+    client.unpause(&admin, &types::PauseGroup::Releases);
+    client.release_funds(&split_id);
+    assert_eq!(token.balance(&creator), 1_000);
+}
+
+#[test]
+fn test_guardian_can_pause() {
+    let (env, client, _split_id, _creator, _p1, _p2, _token) = setup_split();
+    let guardian = Address::generate(&env);
+    client.set_guardian(&guardian);
+
+    client.pause(&guardian, &types::PauseGroup::Refunds);
+    assert!(client.is_paused(&types::PauseGroup::Refunds));
+
+    let stranger = Address::generate(&env);
+    assert!(client.try_pause(&stranger, &types::PauseGroup::Deposits).is_err());
+    assert!(!client.is_paused(&types::PauseGroup::Deposits));
+}
+
+#[test]
+fn test_guardian_cannot_unpause() {
+    let (env, client, _split_id, _creator, _p1, _p2, _token) = setup_split();
+    let guardian = Address::generate(&env);
+    client.set_guardian(&guardian);
+    client.pause(&guardian, &types::PauseGroup::Deposits);
+
+    // The guardian is not a pauser, so it cannot lift the pause
+    assert!(client.try_unpause(&guardian, &types::PauseGroup::Deposits).is_err());
+    assert!(client.is_paused(&types::PauseGroup::Deposits));
+}
+
+// ============================================
+// Admin Transfer and Role Tests
+// ============================================
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let successor = Address::generate(&env);
+    assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoPendingAdmin)));
+
+    client.propose_admin(&successor);
+    assert_eq!(client.get_pending_admin(), Some(successor.clone()));
+    // Nothing changes until the nominee accepts
+    assert_eq!(client.get_admin(), admin);
+
+    client.accept_admin();
+    assert_eq!(client.get_admin(), successor);
+    assert_eq!(client.get_pending_admin(), None);
+    assert!(client.has_role(&types::Role::Admin, &successor));
+    assert!(!client.has_role(&types::Role::Admin, &admin));
+}
+
+#[test]
+fn test_granted_role_gates_entry_point() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let manager = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    assert!(client.try_add_approved_asset(&manager, &asset).is_err());
+
+    client.grant_role(&types::Role::AssetManager, &manager);
+    assert!(client.has_role(&types::Role::AssetManager, &manager));
+    assert!(!client.has_role(&types::Role::Pauser, &manager));
+    client.add_approved_asset(&manager, &asset);
+    assert!(client.is_asset_approved(&asset));
+
+    // The role doesn't reach other areas
+    assert!(client.try_pause(&manager, &types::PauseGroup::Deposits).is_err());
+
+    client.revoke_role(&types::Role::AssetManager, &manager);
+    assert!(!client.has_role(&types::Role::AssetManager, &manager));
+    assert!(client.try_remove_approved_asset(&manager, &asset).is_err());
+}
+
+#[test]
+fn test_pauser_role_can_unpause() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let pauser = Address::generate(&env);
+    client.grant_role(&types::Role::Pauser, &pauser);
+    client.pause(&pauser, &types::PauseGroup::Releases);
+    assert!(client.is_paused(&types::PauseGroup::Releases));
+    client.unpause(&pauser, &types::PauseGroup::Releases);
+    assert!(!client.is_paused(&types::PauseGroup::Releases));
+}
+
+#[test]
+fn test_admin_role_cannot_be_granted() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_grant_role(&types::Role::Admin, &other),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+// ============================================
+// Protocol Fee Tests
+// ============================================
+
+fn fee_config(env: &Env, fee_bps: u32) -> types::FeeConfig {
+    types::FeeConfig {
+        fee_bps,
+        min_fee: 0,
+        max_fee: None,
+        treasury: Address::generate(env),
+        keeper_bounty: 0,
+    }
+}
+
+#[test]
+fn test_fee_config_clamps_fee() {
+    let env = Env::default();
+    let config = types::FeeConfig {
+        min_fee: 5,
+        max_fee: Some(20),
+        ..fee_config(&env, 100)
+    };
+    assert!(config.validate().is_ok());
+
+    assert_eq!(config.fee_for(100, 1_000), 10);
+    // Raised to the minimum, capped at the maximum and at the payout itself
+    assert_eq!(config.fee_for(100, 100), 5);
+    assert_eq!(config.fee_for(100, 10_000), 20);
+    assert_eq!(config.fee_for(100, 3), 3);
+    // No rate, no fee
+    assert_eq!(config.fee_for(0, 1_000), 0);
+
+    assert!(types::FeeConfig { fee_bps: 1_001, ..config.clone() }.validate().is_err());
+    assert!(types::FeeConfig { max_fee: Some(4), ..config }.validate().is_err());
+}
+
+#[test]
+fn test_release_withholds_fee_for_treasury() {
+    let (env, client, split_id, creator, p1, p2, token) = setup_split();
+    let admin = client.get_admin();
+    let config = fee_config(&env, 100);
+    client.set_fee_config(&config);
+
+    client.deposit(&split_id, &p1, &500);
+    let quote = client.get_fee_quote(&split_id);
+    assert_eq!(quote.get(token.address.clone()), Some(5));
+
+    client.deposit(&split_id, &p2, &500);
+    assert_eq!(token.balance(&creator), 990);
+    assert_eq!(client.get_collected_fees(&token.address), 10);
+
+    // The release event carries the fee
+    let released = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            let topic: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+            topic == symbol_short!("funds_rls")
+        })
+        .unwrap();
+    let payload: (Address, i128, i128, u64) = released.2.try_into_val(&env).unwrap();
+    assert_eq!((payload.0, payload.1, payload.2), (creator, 990, 10));
+
+    assert_eq!(client.try_withdraw_fees(&admin, &token.address, &11), Err(Ok(Error::NoFundsAvailable)));
+    client.withdraw_fees(&admin, &token.address, &10);
+    assert_eq!(token.balance(&config.treasury), 10);
+    assert_eq!(client.get_collected_fees(&token.address), 0);
+}
+
+#[test]
+fn test_asset_override_and_exemption() {
+    let (env, client, split_id, creator, p1, p2, token) = setup_split();
+    client.set_fee_config(&fee_config(&env, 100));
+    client.set_asset_fee_rate(&token.address, &Some(300));
+
+    client.deposit(&split_id, &p1, &500);
+    assert_eq!(client.get_fee_quote(&split_id).get(token.address.clone()), Some(15));
+
+    client.set_fee_exempt(&creator, &true);
+    assert_eq!(client.get_fee_quote(&split_id).get(token.address.clone()), Some(0));
+
+    client.deposit(&split_id, &p2, &500);
+    assert_eq!(token.balance(&creator), 1_000);
+    assert_eq!(client.get_collected_fees(&token.address), 0);
+}
+
+// ============================================
+// Escrow Index Tests
+// ============================================
+
+fn insert_indexed_escrow(
+    env: &Env,
+    client: &SplitEscrowContractClient,
+    creator: &Address,
+    participants: &[Address],
+    token: &Address,
+) -> String {
+    let shares: std::vec::Vec<(&Address, i128)> = participants.iter().map(|p| (p, 100)).collect();
+    create_test_split(env, client, token, creator, &shares)
+}
+
+#[test]
+fn test_list_escrows_paginates_in_creation_order() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+    let a = insert_indexed_escrow(&env, &client, &creator, std::slice::from_ref(&p1), &token_id);
+    let b = insert_indexed_escrow(&env, &client, &creator, std::slice::from_ref(&p2), &token_id);
+    let c = insert_indexed_escrow(&env, &client, &creator, &[p1.clone(), p2.clone()], &token_id);
+
+    let first = client.list_escrows_by_creator(&creator, &0, &2);
+    assert_eq!(first.escrows.len(), 2);
+    assert_eq!(first.escrows.get(0).unwrap().split_id, a);
+    assert_eq!(first.escrows.get(1).unwrap().split_id, b);
+    assert_eq!(first.next_cursor, Some(2));
+
+    let second = client.list_escrows_by_creator(&creator, &2, &2);
+    assert_eq!(second.escrows.len(), 1);
+    assert_eq!(second.escrows.get(0).unwrap().split_id, c);
+    assert_eq!(second.next_cursor, None);
+
+    let mine = client.list_escrows_by_participant(&p1, &0, &10);
+    assert_eq!(mine.escrows.len(), 2);
+    assert_eq!(mine.escrows.get(0).unwrap().split_id, a);
+    assert_eq!(mine.escrows.get(1).unwrap().split_id, c);
+
+    // Past the end is just an empty page
+    let past = client.list_escrows_by_participant(&p1, &5, &10);
+    assert_eq!(past.escrows.len(), 0);
+    assert_eq!(past.next_cursor, None);
+}
+
+#[test]
+fn test_status_index_follows_status_changes() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let a = insert_indexed_escrow(&env, &client, &creator, std::slice::from_ref(&p1), &token_id);
+    let b = insert_indexed_escrow(&env, &client, &creator, std::slice::from_ref(&p1), &token_id);
+    assert_eq!(client.list_escrows_by_status(&EscrowStatus::Active, &0, &10).escrows.len(), 2);
+
+    client.cancel_split(&a);
+
+    let active = client.list_escrows_by_status(&EscrowStatus::Active, &0, &10);
+    assert_eq!(active.escrows.len(), 1);
+    assert_eq!(active.escrows.get(0).unwrap().split_id, b);
+    let cancelled = client.list_escrows_by_status(&EscrowStatus::Cancelled, &0, &10);
+    assert_eq!(cancelled.escrows.len(), 1);
+    assert_eq!(cancelled.escrows.get(0).unwrap().split_id, a);
+
+    // Creator and participant views are unaffected by status
+    assert_eq!(client.list_escrows_by_creator(&creator, &0, &10).escrows.len(), 2);
+}
+
+#[test]
+fn test_index_cursors_survive_removals() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let a = insert_indexed_escrow(&env, &client, &creator, std::slice::from_ref(&p1), &token_id);
+    let b = insert_indexed_escrow(&env, &client, &creator, std::slice::from_ref(&p1), &token_id);
+    let c = insert_indexed_escrow(&env, &client, &creator, std::slice::from_ref(&p1), &token_id);
+
+    let first = client.list_escrows_by_status(&EscrowStatus::Active, &0, &2);
+    assert_eq!(first.escrows.get(1).unwrap().split_id, b);
+    assert_eq!(first.next_cursor, Some(2));
+
+    // Removing an earlier entry doesn't shift the next page
+    client.cancel_split(&a);
+    let second = client.list_escrows_by_status(&EscrowStatus::Active, &2, &2);
+    assert_eq!(second.escrows.len(), 1);
+    assert_eq!(second.escrows.get(0).unwrap().split_id, c);
+    assert_eq!(second.next_cursor, None);
+
+    // The emptied slot is skipped rather than refilled
+    let again = client.list_escrows_by_status(&EscrowStatus::Active, &0, &2);
+    assert_eq!(again.escrows.len(), 1);
+    assert_eq!(again.escrows.get(0).unwrap().split_id, b);
+    assert_eq!(again.next_cursor, Some(2));
+}
+
+// ============================================
+// Expiry Sweeper Tests
+// ============================================
+
+#[test]
+fn test_finalize_expired_refunds_everyone() {
+    let (env, client, split_id, _creator, p1, p2, token) = setup_split();
+    let keeper = Address::generate(&env);
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &200);
+
+    assert_eq!(client.try_finalize_expired(&split_id, &keeper), Err(Ok(Error::EscrowNotRefundable)));
+
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+    assert_eq!(client.finalize_expired(&split_id, &keeper), 700);
+
+    assert_eq!(token.balance(&p1), 500);
+    assert_eq!(token.balance(&p2), 500);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Expired);
+    assert_eq!(client.list_escrows_by_status(&EscrowStatus::Expired, &0, &10).escrows.len(), 1);
+
+    let expired = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            let topic: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+            topic == symbol_short!("e_expired")
+        })
+        .unwrap();
+    let payload: (i128, u64) = expired.2.try_into_val(&env).unwrap();
+    assert_eq!(payload.0, 300);
+
+    // Nothing left to sweep
+    assert_eq!(client.try_finalize_expired(&split_id, &keeper), Err(Ok(Error::NoFundsAvailable)));
+}
+
+#[test]
+fn test_finalize_expired_pays_keeper_bounty_once() {
+    let (env, client, split_id, _creator, p1, _p2, token) = setup_split();
+    client.set_fee_config(&types::FeeConfig {
+        keeper_bounty: 5,
+        ..fee_config(&env, 100)
+    });
+
+    // Seed the contract with some collected fees in the default token
+    token::StellarAssetClient::new(&env, &token.address).mint(&client.address, &8);
+    env.as_contract(&client.address, || storage::set_collected_fees(&env, &token.address, 8));
+
+    client.deposit(&split_id, &p1, &500);
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+
+    let keeper = Address::generate(&env);
+    client.finalize_expired(&split_id, &keeper);
+    assert_eq!(token.balance(&keeper), 5);
+    assert_eq!(client.get_collected_fees(&token.address), 3);
+}
+
+#[test]
+fn test_keeper_bounty_needs_and_is_capped_by_refunds() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test(true);
+    initialize_contract(&client, &admin, &token_id);
+    client.set_fee_config(&types::FeeConfig {
+        keeper_bounty: 5,
+        ..fee_config(&env, 100)
+    });
+    token_admin_client.mint(&client.address, &100);
+    env.as_contract(&client.address, || storage::set_collected_fees(&env, &token_id, 100));
+
+    let creator = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    token_admin_client.mint(&p1, &200);
+    let empty = create_test_split(&env, &client, &token_id, &creator, &[(&p1, 500)]);
+    let small = create_test_split(&env, &client, &token_id, &creator, &[(&p1, 500)]);
+    client.deposit(&small, &p1, &200);
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+
+    // Nothing refunded, no bounty
+    let keeper = Address::generate(&env);
+    assert_eq!(client.finalize_expired(&empty, &keeper), 0);
+    assert_eq!(token_client.balance(&keeper), 0);
+
+    // 1% of the 200 refunded, below the configured 5
+    assert_eq!(client.finalize_expired(&small, &keeper), 200);
+    assert_eq!(token_client.balance(&keeper), 2);
+    assert_eq!(client.get_collected_fees(&token_id), 98);
+}
+
+// ============================================
+// Dispute Integration Tests
+// ============================================
+
+/// Stand-in for the dispute-resolution contract whose outcomes tests set directly
+///
+/// Disputes are kept as the field map the real contract's `Dispute` record
+/// encodes to, reduced to the fields the escrow reads.
+#[contract]
+struct MockDisputeContract;
+
+#[contractimpl]
+impl MockDisputeContract {
+    pub fn raise_dispute(env: Env, _split_id: String, raiser: Address, _reason: String) -> String {
+        raiser.require_auth();
+        let count: u64 = env.storage().instance().get(&symbol_short!("count")).unwrap_or(0);
+        env.storage().instance().set(&symbol_short!("count"), &(count + 1));
+
+        let dispute_id = u64_to_string(&env, count);
+        store_mock_dispute(&env, &dispute_id, dispute::DisputeStatus::Voting, None);
+        dispute_id
+    }
+
+    pub fn resolve(env: Env, dispute_id: String, result: dispute::DisputeResult) {
+        store_mock_dispute(&env, &dispute_id, dispute::DisputeStatus::Resolved, Some(result));
+    }
+
+    pub fn get_dispute(env: Env, dispute_id: String) -> Map<Symbol, Val> {
+        env.storage().instance().get(&dispute_id).unwrap()
+    }
+}
+
+fn store_mock_dispute(env: &Env, dispute_id: &String, status: dispute::DisputeStatus, result: Option<dispute::DisputeResult>) {
+    let mut fields: Map<Symbol, Val> = Map::new(env);
+    fields.set(Symbol::new(env, "status"), status.into_val(env));
+    fields.set(Symbol::new(env, "result"), result.into_val(env));
+    env.storage().instance().set(dispute_id, &fields);
+}
+
+fn setup_disputed() -> (
+    Env,
+    SplitEscrowContractClient<'static>,
+    MockDisputeContractClient<'static>,
+    String,
+    Address,
+    Address,
+    Address,
+    token::Client<'static>,
+) {
+    let (env, client, split_id, creator, p1, p2, token) = setup_split();
+
+    let dispute_id = env.register_contract(None, MockDisputeContract);
+    let disputes = MockDisputeContractClient::new(&env, &dispute_id);
+    client.set_dispute_contract(&dispute_id);
+
+    (env, client, disputes, split_id, creator, p1, p2, token)
+}
+
+#[test]
+fn test_open_dispute_blocks_auto_release() {
+    let (env, client, disputes, split_id, creator, p1, p2, token) = setup_disputed();
+    let dispute_id = client.raise_dispute(&split_id, &p1, &String::from_str(&env, "Wrong total"));
+
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &500);
+
+    // Fully funded, but held while the dispute is voting
+    assert_eq!(token.balance(&creator), 0);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Completed);
+    assert_eq!(client.try_release_funds(&split_id), Err(Ok(Error::DisputeOpen)));
+    assert_eq!(client.try_settle_dispute(&split_id), Err(Ok(Error::EscrowNotRefundable)));
+
+    // Only one dispute can be open at a time
+    let reason = String::from_str(&env, "Again");
+    assert_eq!(client.try_raise_dispute(&split_id, &p2, &reason), Err(Ok(Error::DisputeOpen)));
+
+    // Dismissed: the split stands and funds can be released
+    disputes.resolve(&dispute_id, &dispute::DisputeResult::DismissedForRaiser);
+
+    client.release_funds(&split_id);
+    assert_eq!(token.balance(&creator), 1_000);
+}
+
+#[test]
+fn test_upheld_dispute_refunds_participants() {
+    let (env, client, disputes, split_id, creator, p1, p2, token) = setup_disputed();
+    client.deposit(&split_id, &p1, &500);
+
+    let dispute_id = client.raise_dispute(&split_id, &p1, &String::from_str(&env, "Never delivered"));
+    disputes.resolve(&dispute_id, &dispute::DisputeResult::UpheldForRaiser);
+
+    assert_eq!(client.settle_dispute(&split_id), 500);
+    assert_eq!(token.balance(&p1), 500);
+    assert_eq!(token.balance(&p2), 500);
+    assert_eq!(token.balance(&creator), 0);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Cancelled);
+    assert!(client.try_release_funds(&split_id).is_err());
+}
+
+#[test]
+fn test_repeated_disputes_cannot_hold_funds_forever() {
+    let (env, client, disputes, split_id, creator, p1, p2, token) = setup_disputed();
+    let reason = String::from_str(&env, "Wrong total");
+
+    for i in 0..types::MAX_DISPUTES {
+        let dispute_id = client.raise_dispute(&split_id, &p1, &reason);
+        if i == 0 {
+            client.deposit(&split_id, &p1, &500);
+            client.deposit(&split_id, &p2, &500);
+        }
+        assert_eq!(client.try_release_funds(&split_id), Err(Ok(Error::DisputeOpen)));
+        disputes.resolve(&dispute_id, &dispute::DisputeResult::DismissedForRaiser);
+    }
+
+    assert_eq!(
+        client.try_raise_dispute(&split_id, &p1, &reason),
+        Err(Ok(Error::FlagLimitReached))
+    );
+    client.release_funds(&split_id);
+    assert_eq!(token.balance(&creator), 1_000);
+}
+
+#[test]
+fn test_outsiders_cannot_hold_an_escrow() {
+    let (env, client, disputes, split_id, creator, p1, p2, token) = setup_disputed();
+    let stranger = Address::generate(&env);
+    let reason = String::from_str(&env, "Grief");
+
+    assert_eq!(
+        client.try_raise_dispute(&split_id, &stranger, &reason),
+        Err(Ok(Error::ParticipantNotFound))
+    );
+
+    // Raised straight with the dispute contract and upheld: not the escrow's dispute
+    let dispute_id = disputes.raise_dispute(&split_id, &stranger, &reason);
+    disputes.resolve(&dispute_id, &dispute::DisputeResult::UpheldForRaiser);
+
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &500);
+    assert_eq!(token.balance(&creator), 1_000);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Released);
+}
+
+// ============================================
+// Multisig Gate Tests
+// ============================================
+
+/// Register a multisig contract and gate the escrow on it, without a split
+fn gate_on_multisig(
+    env: &Env,
+    client: &SplitEscrowContractClient,
+    split_id: &String,
+) -> multi_sig_splits::MultisigSplitsContractClient<'static> {
+    let multisig_id = env.register_contract(None, multi_sig_splits::MultisigSplitsContract);
+    let multisig = multi_sig_splits::MultisigSplitsContractClient::new(env, &multisig_id);
+    multisig.initialize(&Address::generate(env));
+
+    client.set_multisig_contract(&multisig_id);
+    client.set_multisig_gate(split_id, &true);
+    multisig
+}
+
+fn setup_multisig_gated() -> (
+    Env,
+    SplitEscrowContractClient<'static>,
+    multi_sig_splits::MultisigSplitsContractClient<'static>,
+    String,
+    Address,
+    Address,
+    Address,
+    token::Client<'static>,
+) {
+    let (env, client, split_id, creator, p1, p2, token) = setup_split();
+    let multisig = gate_on_multisig(&env, &client, &split_id);
+    multisig.create_multisig_split(&split_id, &2, &3_600);
+
+    (env, client, multisig, split_id, creator, p1, p2, token)
+}
+
+#[test]
+fn test_gated_release_waits_for_multisig_approval() {
+    let (_env, client, multisig, split_id, creator, p1, p2, token) = setup_multisig_gated();
+
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &500);
+
+    // Funded, but not auto-released without approval
+    assert_eq!(token.balance(&creator), 0);
+    assert_eq!(client.try_release_funds(&split_id), Err(Ok(Error::MultisigNotApproved)));
+
+    // One of two participants isn't a majority
+    multisig.sign_split(&split_id, &p1);
+    assert_eq!(client.try_release_funds(&split_id), Err(Ok(Error::MultisigNotApproved)));
+
+    // The split's own time lock doesn't hold the escrow
+    multisig.sign_split(&split_id, &p2);
+    client.release_funds(&split_id);
+    assert_eq!(token.balance(&creator), 1_000);
+}
+
+#[test]
+fn test_executed_multisig_allows_release() {
+    let (_env, client, multisig, split_id, creator, p1, p2, token) = setup_multisig_gated();
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &500);
+
+    multisig.sign_split(&split_id, &p1);
+    multisig.sign_split(&split_id, &p2);
+    multisig.emergency_override(&split_id);
+    client.release_funds(&split_id);
+    assert_eq!(token.balance(&creator), 1_000);
+}
+
+#[test]
+fn test_multisig_gate_ignores_outside_signers() {
+    let (env, client, multisig, split_id, creator, p1, p2, token) = setup_multisig_gated();
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &500);
+
+    // Strangers (or the creator) meet the split's threshold but aren't participants
+    multisig.sign_split(&split_id, &creator);
+    multisig.sign_split(&split_id, &Address::generate(&env));
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    multisig.execute_split(&split_id);
+
+    assert_eq!(client.try_release_funds(&split_id), Err(Ok(Error::MultisigNotApproved)));
+    assert_eq!(token.balance(&creator), 0);
+}
+
+#[test]
+fn test_multisig_gate_needs_participant_majority() {
+    let (env, client, split_id, creator, p1, p2, token) = setup_split();
+    let multisig = gate_on_multisig(&env, &client, &split_id);
+
+    // A split created with a threshold of one under the escrow's ID
+    multisig.create_multisig_split(&split_id, &1, &1);
+    multisig.sign_split(&split_id, &p1);
+    env.ledger().with_mut(|li| li.timestamp += 1);
+
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &500);
+    assert_eq!(client.try_release_funds(&split_id), Err(Ok(Error::MultisigNotApproved)));
+
+    multisig.sign_split(&split_id, &p2);
+    client.release_funds(&split_id);
+    assert_eq!(token.balance(&creator), 1_000);
+}
+
+#[test]
+fn test_cancelled_multisig_refunds_escrow() {
+    let (env, client, multisig, split_id, creator, p1, p2, token) = setup_multisig_gated();
+    client.deposit(&split_id, &p1, &500);
+    assert_eq!(client.try_settle_multisig(&split_id), Err(Ok(Error::EscrowNotRefundable)));
+
+    multisig.cancel_split(&split_id, &String::from_str(&env, "Vendor fell through"));
+    assert_eq!(client.try_release_partial(&split_id), Err(Ok(Error::MultisigCancelled)));
+
+    assert_eq!(client.settle_multisig(&split_id), 500);
+    assert_eq!(token.balance(&p1), 500);
+    assert_eq!(token.balance(&p2), 500);
+    assert_eq!(token.balance(&creator), 0);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Cancelled);
+}
+
+#[test]
+fn test_squatted_multisig_split_cannot_freeze_release() {
+    let (env, client, split_id, creator, p1, p2, token) = setup_split();
+    let multisig = gate_on_multisig(&env, &client, &split_id);
+
+    // Someone else claims the escrow's ID first with an unreachable threshold and time lock
+    multisig.create_multisig_split(&split_id, &100, &(10 * 365 * 86_400));
+    multisig.sign_split(&split_id, &p1);
+    multisig.sign_split(&split_id, &p2);
+
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &500);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Released);
+    assert_eq!(token.balance(&creator), 1_000);
+}
+
+#[test]
+fn test_gated_escrow_without_multisig_split_refunds_after_cutoff() {
+    let (env, client, split_id, creator, p1, p2, token) = setup_split();
+    gate_on_multisig(&env, &client, &split_id);
+
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &500);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Completed);
+    assert_eq!(client.try_release_funds(&split_id), Err(Ok(Error::MultisigNotApproved)));
+    assert_eq!(client.try_settle_multisig(&split_id), Err(Ok(Error::EscrowNotRefundable)));
+
+    // No approval by the payment cutoff: anyone can hand the deposits back
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+    assert_eq!(client.settle_multisig(&split_id), 1_000);
+    assert_eq!(token.balance(&p1), 500);
+    assert_eq!(token.balance(&p2), 500);
+    assert_eq!(token.balance(&creator), 0);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Cancelled);
+}
+
+#[test]
+fn test_creator_can_cancel_unapproved_gated_escrow() {
+    let (_env, client, multisig, split_id, creator, p1, p2, token) = setup_multisig_gated();
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &500);
+    multisig.sign_split(&split_id, &p1);
+
+    client.cancel_split(&split_id);
+    assert_eq!(client.get_split(&split_id).status, EscrowStatus::Cancelled);
+    assert_eq!(client.claim_refund(&split_id, &p1), 500);
+    assert_eq!(client.claim_refund(&split_id, &p2), 500);
+    assert_eq!(token.balance(&creator), 0);
+}
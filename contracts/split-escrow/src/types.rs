@@ -6,7 +6,7 @@
 //! This module includes both the original types and the enhanced escrow
 //! types as specified in issue #59.
 
-use soroban_sdk::{contracterror, contracttype, Address, Env, String, Vec};
+use soroban_sdk::{contracterror, contracttype, Address, Bytes, BytesN, Env, Map, String, Vec};
 // ============================================
 // Original Types (preserved for compatibility)
 // ============================================
@@ -89,7 +89,6 @@ pub struct Split {
 }
 
 /// Rewards status for user rewards
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
@@ -113,13 +112,63 @@ pub struct UserRewards {
     pub status: RewardsStatus,
 }
 
+impl UserRewards {
+    /// Reward points earned from activity so far
+    ///
+    /// 10 per split created, 5 per split participated in, plus 0.1% of
+    /// the amount transacted.
+    pub fn total_points(&self) -> i128 {
+        let creation_rewards = self.total_splits_created as i128 * 10;
+        let participation_rewards = self.total_splits_participated as i128 * 5;
+        let volume_rewards = self.total_amount_transacted / 1000;
+        creation_rewards + participation_rewards + volume_rewards
+    }
+}
+
+/// How reward points are paid out in tokens
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardsConfig {
+    /// Token rewards are paid in
+    pub reward_token: Address,
+
+    /// Token units paid per reward point
+    pub tokens_per_point: i128,
+
+    /// Length of an emission epoch in seconds
+    pub epoch_length: u64,
+
+    /// Most tokens that can be paid out across all users in one epoch
+    pub epoch_cap: i128,
+}
+
+impl RewardsConfig {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.tokens_per_point <= 0 {
+            return Err("Tokens per point must be positive");
+        }
+        if self.epoch_length == 0 {
+            return Err("Epoch length must be positive");
+        }
+        if self.epoch_cap <= 0 {
+            return Err("Epoch cap must be positive");
+        }
+        Ok(())
+    }
+
+    /// Epoch number containing `timestamp`
+    pub fn epoch_at(&self, timestamp: u64) -> u64 {
+        timestamp / self.epoch_length
+    }
+}
+
 /// User activity tracking for rewards calculation
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct UserActivity {
     pub user: Address,
     pub activity_type: ActivityType,
-    pub split_id: u64,
+    pub split_id: String,
     pub amount: i128,
     pub timestamp: u64,
 }
@@ -158,6 +207,34 @@ pub struct VerificationRequest {
     pub verified_by: Option<Address>,
     pub verified_at: Option<u64>,
     pub rejection_reason: Option<String>,
+    /// Oracles that attested the receipt is genuine
+    pub approvals: Vec<Address>,
+    /// Oracles that attested the receipt is not genuine
+    pub rejections: Vec<Address>,
+}
+
+/// Verification requests each account may submit per escrow
+///
+/// Each pending request holds the escrow's release until it's decided or
+/// times out, so flags are capped to keep funds from being held forever.
+pub const MAX_VERIFICATION_FLAGS: u32 = 2;
+
+/// Disputes each account may raise per escrow
+///
+/// Only one dispute is open at a time, but each holds the release for its
+/// whole voting period, so an account can't keep raising new ones.
+pub const MAX_DISPUTES: u32 = 2;
+
+impl VerificationRequest {
+    /// Check whether a pending request has run past its timeout
+    pub fn is_timed_out(&self, timeout: u64, now: u64) -> bool {
+        self.status == VerificationStatus::Pending && now > self.submitted_at + timeout
+    }
+
+    /// Check whether `oracle` has already attested
+    pub fn has_attested(&self, oracle: &Address) -> bool {
+        self.approvals.contains(oracle) || self.rejections.contains(oracle)
+    }
 }
 
 /// Oracle configuration and settings
@@ -166,31 +243,49 @@ pub struct VerificationRequest {
 pub struct OracleConfig {
     pub required_verifications: u32,
     pub verification_timeout: u64,
+    /// Fresh price submissions needed before a consensus is reported
     pub min_oracles: u32,
     pub oracle_addresses: Vec<Address>,
+    /// Stake (in the default token) an oracle must lock to register
+    pub min_stake: i128,
+    /// Seconds after which a price submission no longer counts
+    pub price_staleness: u64,
 }
 
 /// Atomic swap status
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub enum SwapStatus {
+    /// Participant A's leg is escrowed, waiting for B
     Pending = 0,
     Completed = 1,
     Refunded = 2,
     Expired = 3,
+    /// Both legs are escrowed and the swap can be claimed
+    Locked = 4,
 }
 
 /// Atomic swap contract for instant settlements
+///
+/// Each side escrows its leg in its own token. Revealing the preimage of
+/// `hash_lock` before `time_lock` delivers both legs; after `time_lock`
+/// each side can only get its own leg back.
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct AtomicSwap {
     pub swap_id: String,
     pub participant_a: Address,
     pub participant_b: Address,
+    /// Token participant A pays with
+    pub asset_a: Address,
+    /// Token participant B pays with
+    pub asset_b: Address,
     pub amount_a: i128,
     pub amount_b: i128,
-    pub hash_lock: String,
-    pub secret: Option<String>,
+    /// SHA-256 hash of the secret
+    pub hash_lock: BytesN<32>,
+    /// The secret, once revealed by a claim
+    pub secret: Option<Bytes>,
     pub time_lock: u64,
     pub created_at: u64,
     pub status: SwapStatus,
@@ -209,6 +304,9 @@ pub struct OracleNode {
     pub active: bool,
 }
 
+/// Reputation a newly registered oracle starts with
+pub const INITIAL_ORACLE_REPUTATION: u64 = 100;
+
 /// Price submission from oracle
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -218,6 +316,46 @@ pub struct PriceSubmission {
     pub price: i128,
     pub timestamp: u64,
     pub signature: String,
+    /// Whether this submission has been scored against a consensus
+    pub evaluated: bool,
+}
+
+/// Penalties for oracles whose prices stray from consensus
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlashingConfig {
+    /// Allowed deviation from consensus, in basis points
+    pub deviation_band: u32,
+    /// Share of stake slashed per outlier submission, in basis points
+    pub slash_rate: u32,
+    /// Reputation lost per outlier submission
+    pub reputation_penalty: u64,
+    /// Oracles whose reputation drops below this are deactivated
+    pub reputation_floor: u64,
+    /// Receives slashed stake
+    pub treasury: Address,
+}
+
+impl SlashingConfig {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.slash_rate > MAX_BASIS_POINTS {
+            return Err("Slash rate cannot exceed 10000 basis points");
+        }
+        if self.reputation_floor > INITIAL_ORACLE_REPUTATION {
+            return Err("Reputation floor cannot exceed starting reputation");
+        }
+        Ok(())
+    }
+
+    /// Check whether `price` falls outside the allowed band around `consensus`
+    pub fn is_outlier(&self, price: i128, consensus: i128) -> bool {
+        (price - consensus).abs() * MAX_BASIS_POINTS as i128 > self.deviation_band as i128 * consensus
+    }
+
+    /// Stake to take from an oracle holding `stake`
+    pub fn slash_amount(&self, stake: i128) -> i128 {
+        stake * self.slash_rate as i128 / MAX_BASIS_POINTS as i128
+    }
 }
 
 /// Consensus price data
@@ -231,6 +369,46 @@ pub struct ConsensusPrice {
     pub timestamp: u64,
 }
 
+/// Compute a consensus price from a set of reported prices
+///
+/// The price is the median (mean of the two middle values for an even
+/// count). Confidence is 10000 minus the mean absolute deviation from the
+/// median in basis points, floored at zero, so tightly clustered reports
+/// give high confidence. `prices` must be non-empty and positive.
+pub fn compute_consensus(env: &Env, asset_pair: &String, prices: &Vec<i128>, timestamp: u64) -> ConsensusPrice {
+    // Insertion sort; oracle sets are small
+    let mut sorted: Vec<i128> = Vec::new(env);
+    for price in prices.iter() {
+        let mut index = sorted.len();
+        while index > 0 && sorted.get(index - 1).unwrap() > price {
+            index -= 1;
+        }
+        sorted.insert(index, price);
+    }
+
+    let count = sorted.len();
+    let median = if count % 2 == 1 {
+        sorted.get(count / 2).unwrap()
+    } else {
+        (sorted.get(count / 2 - 1).unwrap() + sorted.get(count / 2).unwrap()) / 2
+    };
+
+    let mut deviation: i128 = 0;
+    for price in sorted.iter() {
+        deviation += (price - median).abs();
+    }
+    let dispersion = deviation * MAX_BASIS_POINTS as i128 / (count as i128 * median);
+    let confidence = (MAX_BASIS_POINTS as i128 - dispersion).max(0);
+
+    ConsensusPrice {
+        asset_pair: asset_pair.clone(),
+        price: median,
+        confidence,
+        participating_oracles: count,
+        timestamp,
+    }
+}
+
 /// Bridge transaction status
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[contracttype]
@@ -242,19 +420,43 @@ pub enum BridgeStatus {
 }
 
 /// Cross-chain bridge transaction
+///
+/// The sender's tokens are locked when the bridge is initiated. A relayer
+/// signature over the bridge, bound to this network and contract,
+/// completes it; if none arrives before `refund_after` the sender can take
+/// the tokens back.
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct BridgeTransaction {
     pub bridge_id: String,
     pub source_chain: String,
     pub destination_chain: String,
+    /// Token locked on this chain
+    pub asset: Address,
     pub amount: i128,
     pub recipient: String,
     pub sender: Address,
     pub created_at: u64,
     pub status: BridgeStatus,
-    pub proof_hash: Option<String>,
+    /// SHA-256 of the relayer signature that completed the bridge (32 bytes)
+    pub proof_hash: Option<Bytes>,
     pub completed_at: Option<u64>,
+    /// Earliest time the sender can reclaim an uncompleted bridge
+    pub refund_after: u64,
+}
+
+/// Relayers trusted to confirm bridge transfers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeConfig {
+    /// Ed25519 public keys of the relayers
+    pub relayers: Vec<BytesN<32>>,
+
+    /// Seconds after initiation before an uncompleted bridge can be refunded
+    pub refund_timeout: u64,
+
+    /// Receives locked tokens once a bridge completes
+    pub vault: Address,
 }
 
 #[contracterror]
@@ -296,10 +498,73 @@ pub enum Error {
     ProofInvalid = 34,
     /// Asset is not on the approved-asset list
     AssetNotApproved = 35,
+    /// Escrow is neither cancelled nor expired
+    EscrowNotRefundable = 36,
+    /// Address already has an oracle node
+    OracleAlreadyRegistered = 37,
+    /// Oracle has already attested to this verification
+    AlreadyAttested = 38,
+    /// Escrow has a receipt verification in progress
+    VerificationPending = 39,
+    /// Escrow's receipt was rejected by the oracles
+    VerificationRejected = 40,
+    /// User's rewards are suspended
+    RewardsSuspended = 41,
+    /// Public key is not a configured bridge relayer
+    RelayerNotAuthorized = 42,
+    /// Caller does not hold the required role
+    Unauthorized = 43,
+    /// No admin transfer has been proposed
+    NoPendingAdmin = 44,
+    /// Escrow has a dispute in voting
+    DisputeOpen = 45,
+    /// A dispute against the escrow was upheld; it can only be refunded
+    DisputeUpheld = 46,
+    /// Gated escrow's multisig split hasn't approved the release yet
+    MultisigNotApproved = 47,
+    /// Gated escrow's multisig split was cancelled; it can only be refunded
+    MultisigCancelled = 48,
+    /// Shares aren't due for auto-collection yet
+    CollectionNotDue = 49,
+    /// Account has used up its verification requests or disputes for this escrow
+    FlagLimitReached = 50,
 }
 
 
 
+/// Access-control roles
+///
+/// The admin implicitly holds every role; the others are granted per
+/// account so day-to-day operations don't need the admin key.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Role {
+    /// Contract owner; changes through the two-step transfer only
+    Admin = 0,
+    /// Manages the approved-asset allowlist
+    AssetManager = 1,
+    /// Configures the oracle network and slashing
+    OracleManager = 2,
+    /// Can pause and unpause entry point groups
+    Pauser = 3,
+    /// Funds and withdraws contract treasuries
+    Treasurer = 4,
+}
+
+/// Groups of entry points that can be paused independently
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PauseGroup {
+    /// Creating and changing splits, and anything that brings funds in
+    Deposits = 0,
+    /// Paying funds out of escrow (releases, vesting, rewards)
+    Releases = 1,
+    /// Returning funds to participants (refunds, cancellation)
+    Refunds = 2,
+}
+
 /// Configuration for the contract
 ///
 /// I'm keeping this minimal for now but it can be extended
@@ -314,6 +579,80 @@ pub struct ContractConfig {
     pub is_paused: bool,
 }
 
+/// Upper bound on the protocol fee rate (10%)
+pub const MAX_PROTOCOL_FEE_BPS: u32 = 1_000;
+
+/// Largest keeper bounty as a share of what a finalisation refunds (1%)
+pub const MAX_KEEPER_BOUNTY_BPS: u32 = 100;
+
+/// Protocol fee taken from each payout at release time
+///
+/// Fees stay in the contract, tracked per asset, until a treasurer
+/// withdraws them to `treasury`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    /// Default fee in basis points; assets can override it
+    pub fee_bps: u32,
+
+    /// Smallest fee charged on a non-zero payout
+    pub min_fee: i128,
+
+    /// Largest fee charged on a single payout, if capped
+    pub max_fee: Option<i128>,
+
+    /// Receives withdrawn fees
+    pub treasury: Address,
+
+    /// Paid from collected default-token fees to whoever finalises an
+    /// expired escrow; zero disables the bounty
+    pub keeper_bounty: i128,
+}
+
+impl FeeConfig {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.fee_bps > MAX_PROTOCOL_FEE_BPS {
+            return Err("Fee rate cannot exceed 1000 basis points");
+        }
+        if self.min_fee < 0 {
+            return Err("Minimum fee cannot be negative");
+        }
+        if self.keeper_bounty < 0 {
+            return Err("Keeper bounty cannot be negative");
+        }
+        if let Some(max_fee) = self.max_fee {
+            if max_fee < self.min_fee {
+                return Err("Maximum fee cannot be below the minimum fee");
+            }
+        }
+        Ok(())
+    }
+
+    /// Fee on a payout of `amount` at `rate` basis points
+    ///
+    /// Clamped to [min_fee, max_fee] and never more than the payout.
+    /// A zero rate means no fee, regardless of the minimum.
+    pub fn fee_for(&self, rate: u32, amount: i128) -> i128 {
+        if rate == 0 || amount <= 0 {
+            return 0;
+        }
+
+        let mut fee = amount * rate as i128 / MAX_BASIS_POINTS as i128;
+        if fee < self.min_fee {
+            fee = self.min_fee;
+        }
+        if let Some(max_fee) = self.max_fee {
+            if fee > max_fee {
+                fee = max_fee;
+            }
+        }
+        if fee > amount {
+            fee = amount;
+        }
+        fee
+    }
+}
+
 // ============================================
 // Insurance Types
 // ============================================
@@ -373,6 +712,42 @@ pub struct InsurancePolicy {
     
     /// Timestamp when the insurance expires
     pub expires_at: u64,
+
+    /// Total paid out on this policy so far
+    pub paid_out: i128,
+}
+
+impl InsurancePolicy {
+    /// Coverage not yet paid out
+    pub fn remaining_coverage(&self) -> i128 {
+        self.coverage_amount - self.paid_out
+    }
+}
+
+/// Pricing and terms for new insurance policies
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsuranceConfig {
+    /// Premium as basis points of coverage
+    pub premium_rate: u32,
+
+    /// How long after the escrow deadline claims can still be filed
+    pub claim_window: u64,
+}
+
+impl InsuranceConfig {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.premium_rate == 0 || self.premium_rate > MAX_BASIS_POINTS {
+            return Err("Premium rate must be between 1 and 10000 basis points");
+        }
+        Ok(())
+    }
+
+    /// Premium for `coverage`, rounded up so the pool never undercharges
+    pub fn premium_for(&self, coverage: i128) -> i128 {
+        let scaled = coverage * self.premium_rate as i128;
+        (scaled + MAX_BASIS_POINTS as i128 - 1) / MAX_BASIS_POINTS as i128
+    }
 }
 
 /// Insurance claim for split protection
@@ -440,6 +815,9 @@ pub struct EscrowParticipant {
     /// The participant's Stellar address
     pub address: Address,
 
+    /// The token contract this participant pays with (issue #201)
+    pub asset: Address,
+
     /// The amount this participant owes
     pub amount_owed: i128,
 
@@ -448,6 +826,525 @@ pub struct EscrowParticipant {
 
     /// Timestamp when the participant fully paid (None if not yet paid)
     pub paid_at: Option<u64>,
+
+    /// Whether the participant pre-authorised `collect_due` to pull their
+    /// share through a token allowance instead of depositing manually
+    pub auto_collect: bool,
+
+    /// Late-payment surcharge added to `amount_owed` during the grace period
+    pub late_fee: i128,
+}
+
+/// How a payee's cut of a release is computed
+///
+/// Fixed allocations are paid first; basis-point allocations are then
+/// taken from whatever is left of the release amount.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayoutAllocation {
+    /// Share of the post-fixed remainder, in basis points (10_000 = 100%)
+    BasisPoints(u32),
+    /// Exact amount in the escrow's token units
+    Fixed(i128),
+}
+
+/// A recipient of released escrow funds
+///
+/// I'm letting an escrow route its payout to several addresses so a group
+/// dinner can pay the restaurant, the tip jar and the organiser at once.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Payee {
+    /// Address that receives this cut of the release
+    pub recipient: Address,
+
+    /// How this payee's cut is computed
+    pub allocation: PayoutAllocation,
+}
+
+/// Linear vesting schedule for streaming releases
+///
+/// Collected funds vest to the creator linearly between `start` and
+/// `end`. Nothing is claimable before `cliff`; at the cliff the amount
+/// accrued since `start` becomes claimable at once.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    /// Unix timestamp when vesting begins
+    pub start: u64,
+
+    /// Unix timestamp before which nothing can be claimed
+    pub cliff: u64,
+
+    /// Unix timestamp when everything collected is fully vested
+    pub end: u64,
+}
+
+impl VestingSchedule {
+    /// Validate the schedule ordering
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.start >= self.end {
+            return Err("Vesting start must be before end");
+        }
+
+        if self.cliff < self.start || self.cliff > self.end {
+            return Err("Vesting cliff must be within the schedule");
+        }
+
+        Ok(())
+    }
+
+    /// Portion of `amount` that has vested at `now` (rounded down)
+    pub fn vested_amount(&self, amount: i128, now: u64) -> i128 {
+        if now < self.cliff {
+            return 0;
+        }
+
+        if now >= self.end {
+            return amount;
+        }
+
+        let elapsed = (now - self.start) as i128;
+        let duration = (self.end - self.start) as i128;
+        amount * elapsed / duration
+    }
+}
+
+/// How a funded escrow pays out to the creator
+///
+/// An enum rather than `Option<VestingSchedule>`: contract types can't sit
+/// inside an `Option` field of another contract type under SDK 21 testutils.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReleaseSchedule {
+    /// Everything is released at once when the escrow is funded
+    Immediate,
+    /// Funds stream to the creator over the schedule
+    Vesting(VestingSchedule),
+}
+
+/// Recurrence settings for escrows that repeat every period (rent, subscriptions)
+///
+/// The participant set and shares carry over from cycle to cycle; each
+/// cycle gets a fresh deadline `interval` seconds after the previous one.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecurrenceConfig {
+    /// Length of each period in seconds
+    pub interval: u64,
+
+    /// Total number of cycles, including the first
+    pub max_cycles: u32,
+
+    /// Zero-based index of the cycle currently open
+    pub current_cycle: u32,
+}
+
+/// Whether an escrow reopens for another period after it closes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Recurrence {
+    /// The escrow runs for a single period
+    OneOff,
+    /// The escrow repeats every period until its cycles run out
+    Recurring(RecurrenceConfig),
+}
+
+/// Closed-period summary for a recurring escrow
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CycleRecord {
+    /// Zero-based cycle index
+    pub cycle: u32,
+
+    /// Deadline the cycle ran against
+    pub deadline: u64,
+
+    /// Amount owed for the cycle
+    pub total_amount: i128,
+
+    /// Amount collected by the time the cycle closed
+    pub amount_collected: i128,
+
+    /// Final status (Released when paid out, Expired when underfunded)
+    pub status: EscrowStatus,
+
+    /// Unix timestamp when the cycle was rolled over
+    pub closed_at: u64,
+}
+
+/// Why an allowance-based collection failed for a participant
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CollectionFailureReason {
+    /// Allowance granted to the escrow is below the amount due
+    InsufficientAllowance,
+    /// Participant's token balance is below the amount due
+    InsufficientBalance,
+    /// The token contract rejected the transfer
+    TransferFailed,
+}
+
+/// Most recent failed auto-collection attempt for a participant
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CollectionFailure {
+    /// Participant whose share couldn't be pulled
+    pub participant: Address,
+
+    /// Amount that was due
+    pub amount_due: i128,
+
+    /// Why the pull failed
+    pub reason: CollectionFailureReason,
+
+    /// Unix timestamp of the attempt
+    pub attempted_at: u64,
+}
+
+/// How the late-payment surcharge is computed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PenaltySchedule {
+    /// One-off surcharge in the participant's asset units
+    Flat(i128),
+    /// Surcharge per started day late, in basis points of the original share
+    BasisPointsPerDay(u32),
+}
+
+/// Grace period and penalty settings for late payments
+///
+/// Deposits after `deadline` but within `grace_period` seconds of it are
+/// still accepted, with a surcharge added to what the participant owes.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LatePolicy {
+    /// Seconds after the deadline during which late deposits are accepted
+    pub grace_period: u64,
+
+    /// How the surcharge is computed
+    pub penalty: PenaltySchedule,
+
+    /// Where penalties are paid on release (None = the creator)
+    pub treasury: Option<Address>,
+}
+
+/// What happens to deposits once the deadline passes
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum DeadlinePolicy {
+    /// No deposits are accepted after the deadline
+    Hard,
+    /// Late deposits are accepted with a surcharge during a grace period
+    Grace(LatePolicy),
+}
+
+/// Seconds in a day, used for per-day penalties
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// How long before the deadline auto-collected shares fall due
+pub const COLLECTION_WINDOW: u64 = SECONDS_PER_DAY;
+
+impl LatePolicy {
+    /// Validate the policy values
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.grace_period == 0 {
+            return Err("Grace period must be positive");
+        }
+
+        match self.penalty {
+            PenaltySchedule::Flat(amount) if amount <= 0 => {
+                Err("Flat penalty must be positive")
+            }
+            PenaltySchedule::BasisPointsPerDay(bps) if bps == 0 || bps > MAX_BASIS_POINTS => {
+                Err("Daily penalty must be between 1 and 10000 basis points")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Total surcharge owed on `share` when paying `seconds_late` after the deadline
+    pub fn surcharge(&self, share: i128, seconds_late: u64) -> i128 {
+        if seconds_late == 0 {
+            return 0;
+        }
+
+        match self.penalty {
+            PenaltySchedule::Flat(amount) => amount,
+            PenaltySchedule::BasisPointsPerDay(bps) => {
+                let days_late = seconds_late.div_ceil(SECONDS_PER_DAY) as i128;
+                share * bps as i128 * days_late / MAX_BASIS_POINTS as i128
+            }
+        }
+    }
+}
+
+/// A participant's share in a proposed amendment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParticipantShare {
+    /// The participant's Stellar address
+    pub address: Address,
+
+    /// The token contract the participant will pay with
+    pub asset: Address,
+
+    /// The amount the participant will owe (before any late fee)
+    pub amount_owed: i128,
+}
+
+/// A pending change to a live escrow's participant set
+///
+/// I'm requiring every affected participant (added, removed, or whose
+/// share or asset changes) to co-sign before the change is applied.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Amendment {
+    /// The creator who proposed the change
+    pub proposer: Address,
+
+    /// The full participant set after the amendment
+    pub shares: Vec<ParticipantShare>,
+
+    /// Participants who must approve before it applies
+    pub required_approvals: Vec<Address>,
+
+    /// Participants who have approved so far
+    pub approvals: Vec<Address>,
+
+    /// Unix timestamp when the amendment was proposed
+    pub proposed_at: u64,
+}
+
+impl Amendment {
+    /// Check whether every affected participant has approved
+    pub fn is_fully_approved(&self) -> bool {
+        for i in 0..self.required_approvals.len() {
+            if !self.approvals.contains(self.required_approvals.get(i).unwrap()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// What to do when a deposit exceeds what the participant still owes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OverpaymentPolicy {
+    /// Reject the deposit (original behaviour)
+    Reject,
+    /// Accept only the remaining amount owed
+    Cap,
+    /// Accept everything and hold the excess as a withdrawable credit
+    Credit,
+}
+
+/// A payment recorded against an escrow, noting who actually paid
+///
+/// Refunds are recorded as negative entries so the log nets out to what
+/// each payer still has in the escrow for each beneficiary.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentRecord {
+    /// Address the tokens came from (and go back to on refund)
+    pub payer: Address,
+
+    /// Participant whose share the payment counted toward
+    pub beneficiary: Address,
+
+    /// Amount credited (negative for refunds)
+    pub amount: i128,
+
+    /// Unix timestamp of the payment or refund
+    pub timestamp: u64,
+}
+
+/// Work out how to unwind part of a participant's payments by payer
+///
+/// `unwound` is how much of the beneficiary's `amount_paid` is being
+/// reversed and `refund` how much of it is actually returned (less when
+/// funds were already partially released). Each payer gets their pro-rata
+/// share; any amount not in the log is treated as paid by the beneficiary.
+/// Returns `(payer, unwound_share, refund_share)` entries, with rounding
+/// dust assigned to the last one.
+pub fn payer_refunds(
+    env: &Env,
+    log: &Vec<PaymentRecord>,
+    beneficiary: &Address,
+    amount_paid: i128,
+    unwound: i128,
+    refund: i128,
+) -> Vec<(Address, i128, i128)> {
+    let mut contributions: Map<Address, i128> = Map::new(env);
+    for record in log.iter() {
+        if record.beneficiary == *beneficiary {
+            let current = contributions.get(record.payer.clone()).unwrap_or(0);
+            contributions.set(record.payer, current + record.amount);
+        }
+    }
+
+    let mut basis: i128 = 0;
+    let mut payers: Vec<(Address, i128)> = Vec::new(env);
+    for (payer, contributed) in contributions.iter() {
+        if contributed > 0 {
+            basis += contributed;
+            payers.push_back((payer, contributed));
+        }
+    }
+
+    if amount_paid > basis {
+        let unlogged = amount_paid - basis;
+        let mut merged: Vec<(Address, i128)> = Vec::new(env);
+        let mut found = false;
+        for (payer, contributed) in payers.iter() {
+            if payer == *beneficiary {
+                merged.push_back((payer, contributed + unlogged));
+                found = true;
+            } else {
+                merged.push_back((payer, contributed));
+            }
+        }
+        if !found {
+            merged.push_back((beneficiary.clone(), unlogged));
+        }
+        payers = merged;
+        basis = amount_paid;
+    }
+
+    let mut plan: Vec<(Address, i128, i128)> = Vec::new(env);
+    if basis <= 0 {
+        return plan;
+    }
+
+    let mut unwound_left = unwound;
+    let mut refund_left = refund;
+    let last = payers.len() - 1;
+    for (i, (payer, contributed)) in payers.iter().enumerate() {
+        let (unwound_share, refund_share) = if i as u32 == last {
+            (unwound_left, refund_left)
+        } else {
+            (contributed * unwound / basis, contributed * refund / basis)
+        };
+        unwound_left -= unwound_share;
+        refund_left -= refund_share;
+        if unwound_share > 0 || refund_share > 0 {
+            plan.push_back((payer, unwound_share, refund_share));
+        }
+    }
+
+    plan
+}
+
+/// Largest page returned by the escrow listing views
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// One page of an escrow listing
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EscrowPage {
+    /// Escrows on this page, in index order
+    pub escrows: Vec<SplitEscrow>,
+
+    /// Cursor for the next page, or None on the last page
+    pub next_cursor: Option<u32>,
+}
+
+/// A participant's weighted claim on a line item
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ItemAssignment {
+    /// The participant sharing the item
+    pub participant: Address,
+
+    /// Relative weight of this participant's portion (e.g. 2 for two of three slices)
+    pub weight: u32,
+}
+
+/// One line of an itemised bill
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineItem {
+    /// What was bought, as printed on the receipt
+    pub description: String,
+
+    /// Price of a single unit
+    pub unit_price: i128,
+
+    /// Number of units
+    pub quantity: u32,
+
+    /// Who is paying for the item, and in what proportion
+    pub assignments: Vec<ItemAssignment>,
+}
+
+impl LineItem {
+    /// Total cost of the line (unit price times quantity)
+    pub fn line_total(&self) -> i128 {
+        self.unit_price * self.quantity as i128
+    }
+}
+
+/// Derive each participant's share from an itemised bill
+///
+/// Each line is divided by weight, rounding down; the leftover units go
+/// one at a time to the item's assignees in the order they are listed, so
+/// the same bill always produces the same shares. Returns the shares in
+/// the same order as `participants`.
+pub fn shares_from_line_items(
+    env: &Env,
+    items: &Vec<LineItem>,
+    participants: &Vec<Address>,
+) -> Result<Vec<i128>, &'static str> {
+    let mut owed: Map<Address, i128> = Map::new(env);
+    for participant in participants.iter() {
+        owed.set(participant, 0);
+    }
+
+    for item in items.iter() {
+        if item.unit_price <= 0 || item.quantity == 0 {
+            return Err("Line item price and quantity must be positive");
+        }
+        if item.assignments.is_empty() {
+            return Err("Line item must be assigned to at least one participant");
+        }
+
+        let mut total_weight: i128 = 0;
+        for assignment in item.assignments.iter() {
+            if assignment.weight == 0 {
+                return Err("Line item weights must be positive");
+            }
+            if !owed.contains_key(assignment.participant.clone()) {
+                return Err("Line item assigned to a non-participant");
+            }
+            total_weight += assignment.weight as i128;
+        }
+
+        let line_total = item.line_total();
+        let mut leftover = line_total;
+        for assignment in item.assignments.iter() {
+            let portion = line_total * assignment.weight as i128 / total_weight;
+            leftover -= portion;
+            let current = owed.get(assignment.participant.clone()).unwrap();
+            owed.set(assignment.participant, current + portion);
+        }
+
+        // leftover < number of assignees, so one pass is enough
+        for assignment in item.assignments.iter() {
+            if leftover == 0 {
+                break;
+            }
+            let current = owed.get(assignment.participant.clone()).unwrap();
+            owed.set(assignment.participant, current + 1);
+            leftover -= 1;
+        }
+    }
+
+    let mut shares = Vec::new(env);
+    for participant in participants.iter() {
+        shares.push_back(owed.get(participant).unwrap());
+    }
+    Ok(shares)
 }
 
 /// Main escrow structure matching issue #59 specification
@@ -486,19 +1383,131 @@ pub struct SplitEscrow {
 
     /// Unix timestamp when the escrow was created
     pub created_at: u64,
+
+    /// Payout routing for release (empty = everything goes to the creator)
+    pub payees: Vec<Payee>,
+
+    /// Per-asset ledger of funds collected (token address -> amount)
+    pub asset_collected: Map<Address, i128>,
+
+    /// Per-asset ledger of funds already paid out (token address -> amount)
+    pub asset_released: Map<Address, i128>,
+
+    /// Whether funds are released at once or stream over a vesting schedule
+    pub release_schedule: ReleaseSchedule,
+
+    /// Period settings for recurring escrows
+    pub recurrence: Recurrence,
+
+    /// Grace period and penalties for late deposits
+    pub deadline_policy: DeadlinePolicy,
+
+    /// How deposits above the remaining amount owed are handled
+    pub overpayment_policy: OverpaymentPolicy,
+
+    /// Itemised bill the shares were derived from (empty if not itemised)
+    pub line_items: Vec<LineItem>,
+
+    /// Whether releases wait for approval from the multisig contract
+    pub multisig_gated: bool,
 }
 
 // ============================================
 // Validation Helpers
 // ============================================
 
+/// Basis-point denominator (10_000 = 100%)
+pub const MAX_BASIS_POINTS: u32 = 10_000;
+
 impl SplitEscrow {
+    /// Vesting schedule of a streaming escrow, if it has one
+    pub fn vesting(&self) -> Option<VestingSchedule> {
+        match &self.release_schedule {
+            ReleaseSchedule::Vesting(schedule) => Some(schedule.clone()),
+            ReleaseSchedule::Immediate => None,
+        }
+    }
+
+    /// Period settings of a recurring escrow, if it repeats
+    pub fn recurrence_config(&self) -> Option<RecurrenceConfig> {
+        match &self.recurrence {
+            Recurrence::Recurring(config) => Some(config.clone()),
+            Recurrence::OneOff => None,
+        }
+    }
+
+    /// Grace period and penalty settings, if late deposits are accepted
+    pub fn late_policy(&self) -> Option<LatePolicy> {
+        match &self.deadline_policy {
+            DeadlinePolicy::Grace(policy) => Some(policy.clone()),
+            DeadlinePolicy::Hard => None,
+        }
+    }
+
     /// Check if the escrow has expired based on current timestamp
     ///
     /// I'm providing this helper so contract logic can easily
     /// check expiry without duplicating the comparison everywhere.
     pub fn is_expired(&self, current_timestamp: u64) -> bool {
-        current_timestamp > self.deadline && self.status == EscrowStatus::Active
+        current_timestamp > self.payment_cutoff() && self.status == EscrowStatus::Active
+    }
+
+    /// Last timestamp at which deposits are accepted (deadline plus grace)
+    pub fn payment_cutoff(&self) -> u64 {
+        match self.late_policy() {
+            Some(policy) => self.deadline + policy.grace_period,
+            None => self.deadline,
+        }
+    }
+
+    /// Participant signatures a gated escrow's multisig split needs (a majority)
+    pub fn multisig_quorum(&self) -> u32 {
+        self.participants.len() / 2 + 1
+    }
+
+    /// First timestamp at which `collect_due` may pull shares
+    pub fn collection_due_at(&self) -> u64 {
+        self.deadline.saturating_sub(COLLECTION_WINDOW)
+    }
+
+    /// Bring a participant's late surcharge up to date for a payment at `now`
+    ///
+    /// Adds any newly accrued surcharge to the participant's `amount_owed`
+    /// and `late_fee` and returns the increase, which the caller must add
+    /// to the escrow's `total_amount`. Paid-up participants aren't charged.
+    pub fn apply_late_surcharge(&self, participant: &mut EscrowParticipant, now: u64) -> i128 {
+        let policy = match self.late_policy() {
+            Some(policy) => policy,
+            None => return 0,
+        };
+
+        if now <= self.deadline || participant.has_fully_paid() {
+            return 0;
+        }
+
+        let share = participant.amount_owed - participant.late_fee;
+        let surcharge = policy.surcharge(share, now - self.deadline);
+        if surcharge <= participant.late_fee {
+            return 0;
+        }
+
+        let increase = surcharge - participant.late_fee;
+        participant.late_fee = surcharge;
+        participant.amount_owed += increase;
+        increase
+    }
+
+    /// Late fees owed per asset across all participants
+    pub fn late_fees_by_asset(&self, env: &Env) -> Map<Address, i128> {
+        let mut fees: Map<Address, i128> = Map::new(env);
+        for i in 0..self.participants.len() {
+            let p = self.participants.get(i).unwrap();
+            if p.late_fee > 0 {
+                let current = fees.get(p.asset.clone()).unwrap_or(0);
+                fees.set(p.asset.clone(), current + p.late_fee);
+            }
+        }
+        fees
     }
 
     /// Validate escrow invariants
@@ -506,6 +1515,7 @@ impl SplitEscrow {
     /// I'm checking that the escrow data is internally consistent:
     /// - Amount collected doesn't exceed total
     /// - Participant amounts sum correctly
+    /// - Each participant appears only once
     /// - Status matches the payment state
     pub fn validate(&self) -> Result<(), &'static str> {
         // Check amount bounds
@@ -530,6 +1540,11 @@ impl SplitEscrow {
             if p.amount_paid > p.amount_owed {
                 return Err("Participant overpaid");
             }
+
+            // A second entry for the same address could never be found again
+            if self.participants.iter().take(i as usize).any(|q| q.address == p.address) {
+                return Err("Duplicate participant");
+            }
         }
 
         // Total owed should match escrow total
@@ -542,9 +1557,259 @@ impl SplitEscrow {
             return Err("Collected amount mismatch");
         }
 
+        self.validate_payees()
+    }
+
+    /// Validate the payout routing
+    ///
+    /// Fixed amounts can't exceed the escrow total and basis points
+    /// can't exceed 100%. Anything left unallocated goes to the creator.
+    pub fn validate_payees(&self) -> Result<(), &'static str> {
+        let mut fixed_total: i128 = 0;
+        let mut bps_total: u32 = 0;
+
+        for i in 0..self.payees.len() {
+            let payee = self.payees.get(i).unwrap();
+            match payee.allocation {
+                PayoutAllocation::Fixed(amount) => {
+                    if amount <= 0 {
+                        return Err("Fixed payee amount must be positive");
+                    }
+                    fixed_total += amount;
+                }
+                PayoutAllocation::BasisPoints(bps) => {
+                    if bps == 0 {
+                        return Err("Payee basis points must be positive");
+                    }
+                    bps_total += bps;
+                }
+            }
+        }
+
+        if fixed_total > self.total_amount {
+            return Err("Fixed payee amounts exceed total");
+        }
+
+        // A fixed amount is only meaningful in a single token
+        if fixed_total > 0 && self.is_multi_asset() {
+            return Err("Fixed payee amounts require a single-asset escrow");
+        }
+
+        if bps_total > MAX_BASIS_POINTS {
+            return Err("Payee basis points exceed 100%");
+        }
+
         Ok(())
     }
 
+    /// Check whether participants pay with more than one token
+    pub fn is_multi_asset(&self) -> bool {
+        if self.participants.is_empty() {
+            return false;
+        }
+        let first = self.participants.get(0).unwrap().asset;
+        for i in 1..self.participants.len() {
+            if self.participants.get(i).unwrap().asset != first {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Record a deposit against the per-asset ledger
+    pub fn record_asset_deposit(&mut self, asset: &Address, amount: i128) {
+        let current = self.asset_collected.get(asset.clone()).unwrap_or(0);
+        self.asset_collected.set(asset.clone(), current + amount);
+    }
+
+    /// Record a refund against the per-asset ledger
+    ///
+    /// `amount_paid` is what the participant originally deposited and
+    /// `refunded` is what they got back; the difference was already paid
+    /// out and leaves the released side of the ledger with them.
+    pub fn record_asset_refund(&mut self, asset: &Address, amount_paid: i128, refunded: i128) {
+        let collected = self.asset_collected.get(asset.clone()).unwrap_or(0);
+        self.asset_collected.set(asset.clone(), collected - amount_paid);
+
+        let already_released = amount_paid - refunded;
+        if already_released > 0 {
+            let released = self.asset_released.get(asset.clone()).unwrap_or(0);
+            self.asset_released.set(asset.clone(), released - already_released);
+        }
+    }
+
+    /// Amount refundable to a participant who paid `amount_paid` in `asset`
+    ///
+    /// If part of that asset has already been paid out (partial or vested
+    /// release), the participant gets back their pro-rata share of what
+    /// is still held, rounded down.
+    pub fn refundable_amount(&self, asset: &Address, amount_paid: i128) -> i128 {
+        let collected = self.asset_collected.get(asset.clone()).unwrap_or(0);
+        if collected <= 0 {
+            return 0;
+        }
+
+        let released = self.asset_released.get(asset.clone()).unwrap_or(0);
+        if released <= 0 {
+            return amount_paid;
+        }
+
+        amount_paid * (collected - released) / collected
+    }
+
+    /// Vested funds per asset that have not been claimed yet
+    ///
+    /// Returns an empty map for escrows without a vesting schedule.
+    pub fn vested_unreleased_by_asset(&self, env: &Env, now: u64) -> Map<Address, i128> {
+        let mut claimable: Map<Address, i128> = Map::new(env);
+        if let Some(schedule) = self.vesting() {
+            for (asset, collected) in self.asset_collected.iter() {
+                let vested = schedule.vested_amount(collected, now);
+                let released = self.asset_released.get(asset.clone()).unwrap_or(0);
+                if vested > released {
+                    claimable.set(asset, vested - released);
+                }
+            }
+        }
+        claimable
+    }
+
+    /// Record a payout against the per-asset ledger
+    pub fn record_asset_release(&mut self, asset: &Address, amount: i128) {
+        let current = self.asset_released.get(asset.clone()).unwrap_or(0);
+        self.asset_released.set(asset.clone(), current + amount);
+    }
+
+    /// Funds held per asset that have not been paid out yet
+    pub fn unreleased_by_asset(&self, env: &Env) -> Map<Address, i128> {
+        let mut held: Map<Address, i128> = Map::new(env);
+        for (asset, collected) in self.asset_collected.iter() {
+            let released = self.asset_released.get(asset.clone()).unwrap_or(0);
+            if collected > released {
+                held.set(asset, collected - released);
+            }
+        }
+        held
+    }
+
+    /// Split a release amount across the configured payees
+    ///
+    /// Returns `(recipient, amount)` pairs. Fixed cuts come off the top,
+    /// basis-point cuts are taken from the remainder (rounded down), and
+    /// any dust or unallocated amount is routed to the creator.
+    pub fn payout_plan(&self, env: &Env, amount: i128) -> Vec<(Address, i128)> {
+        let mut plan: Vec<(Address, i128)> = Vec::new(env);
+        let mut remaining = amount;
+
+        for i in 0..self.payees.len() {
+            let payee = self.payees.get(i).unwrap();
+            if let PayoutAllocation::Fixed(fixed) = payee.allocation {
+                let cut = if fixed > remaining { remaining } else { fixed };
+                if cut > 0 {
+                    plan.push_back((payee.recipient.clone(), cut));
+                    remaining -= cut;
+                }
+            }
+        }
+
+        let bps_base = remaining;
+        for i in 0..self.payees.len() {
+            let payee = self.payees.get(i).unwrap();
+            if let PayoutAllocation::BasisPoints(bps) = payee.allocation {
+                let cut = bps_base * bps as i128 / MAX_BASIS_POINTS as i128;
+                if cut > 0 {
+                    plan.push_back((payee.recipient.clone(), cut));
+                    remaining -= cut;
+                }
+            }
+        }
+
+        if remaining > 0 {
+            plan.push_back((self.creator.clone(), remaining));
+        }
+
+        plan
+    }
+
+    /// Find a participant by address
+    pub fn find_participant(&self, address: &Address) -> Option<EscrowParticipant> {
+        for i in 0..self.participants.len() {
+            let p = self.participants.get(i).unwrap();
+            if p.address == *address {
+                return Some(p);
+            }
+        }
+        None
+    }
+
+    /// Participants who must co-sign a change to the given share set
+    ///
+    /// Anyone added, removed, or whose share or asset changes is affected.
+    pub fn affected_by(&self, env: &Env, shares: &Vec<ParticipantShare>) -> Vec<Address> {
+        let mut affected = Vec::new(env);
+
+        for i in 0..shares.len() {
+            let share = shares.get(i).unwrap();
+            let changed = match self.find_participant(&share.address) {
+                Some(p) => p.asset != share.asset || p.amount_owed - p.late_fee != share.amount_owed,
+                None => true,
+            };
+            if changed && !affected.contains(&share.address) {
+                affected.push_back(share.address.clone());
+            }
+        }
+
+        for i in 0..self.participants.len() {
+            let p = self.participants.get(i).unwrap();
+            let kept = shares.iter().any(|share| share.address == p.address);
+            if !kept && !affected.contains(&p.address) {
+                affected.push_back(p.address.clone());
+            }
+        }
+
+        affected
+    }
+
+    /// Check whether a recurring escrow has another cycle to open
+    pub fn has_next_cycle(&self) -> bool {
+        match self.recurrence_config() {
+            Some(recurrence) => recurrence.current_cycle + 1 < recurrence.max_cycles,
+            None => false,
+        }
+    }
+
+    /// Reset payment state and open the next cycle of a recurring escrow
+    ///
+    /// Participants and their shares carry over; payments, the per-asset
+    /// ledger and the deadline are reset for the new period. The new
+    /// deadline is one interval after the later of now and the old deadline,
+    /// so a late rollover still leaves a full period to pay in.
+    pub fn open_next_cycle(&mut self, env: &Env) {
+        let mut recurrence = self.recurrence_config().expect("Escrow is not recurring");
+
+        let mut participants = Vec::new(env);
+        let mut late_fees: i128 = 0;
+        for i in 0..self.participants.len() {
+            let mut p = self.participants.get(i).unwrap();
+            p.amount_paid = 0;
+            p.paid_at = None;
+            p.amount_owed -= p.late_fee;
+            late_fees += p.late_fee;
+            p.late_fee = 0;
+            participants.push_back(p);
+        }
+
+        recurrence.current_cycle += 1;
+        self.participants = participants;
+        self.total_amount -= late_fees;
+        self.amount_collected = 0;
+        self.asset_collected = Map::new(env);
+        self.asset_released = Map::new(env);
+        self.deadline = self.deadline.max(env.ledger().timestamp()) + recurrence.interval;
+        self.status = EscrowStatus::Active;
+        self.recurrence = Recurrence::Recurring(recurrence);
+    }
+
     /// Check if all participants have fully paid
     pub fn is_fully_funded(&self) -> bool {
         self.amount_collected >= self.total_amount
@@ -598,12 +1863,15 @@ impl EscrowParticipant {
 
 impl EscrowParticipant {
     /// Create a new participant with zero payments
-    pub fn new(address: Address, amount_owed: i128) -> Self {
+    pub fn new(address: Address, asset: Address, amount_owed: i128) -> Self {
         Self {
             address,
+            asset,
             amount_owed,
             amount_paid: 0,
             paid_at: None,
+            auto_collect: false,
+            late_fee: 0,
         }
     }
 }
@@ -611,6 +1879,7 @@ impl EscrowParticipant {
 /// Helper to create a new escrow with default values
 ///
 /// I'm providing this to ensure escrows are created consistently.
+#[allow(dead_code)]
 pub fn create_escrow(
     env: &Env,
     split_id: String,
@@ -631,14 +1900,14 @@ pub fn create_escrow(
         status: EscrowStatus::Active,
         deadline,
         created_at: env.ledger().timestamp(),
+        payees: Vec::new(env),
+        asset_collected: Map::new(env),
+        asset_released: Map::new(env),
+        release_schedule: ReleaseSchedule::Immediate,
+        recurrence: Recurrence::OneOff,
+        deadline_policy: DeadlinePolicy::Hard,
+        overpayment_policy: OverpaymentPolicy::Reject,
+        line_items: Vec::new(env),
+        multisig_gated: false,
     }
 }
-
-
-
-pub fn emit_partial_payment_received(env: &Env, participant: Address, amount: i128, remaining: i128) {
-    env.events().publish(
-        (Symbol::new("PartialPaymentReceived"), participant),
-        (amount, remaining),
-    );
-}
\ No newline at end of file